@@ -1,5 +1,6 @@
 use crate::client_key::StringClientKey;
 use crate::server_key::StringServerKey;
+use serde::{Deserialize, Serialize};
 use tfhe::integer::{gen_keys_radix, RadixCiphertext};
 use tfhe::shortint::prelude::{
     DecompositionBaseLog, DecompositionLevelCount, GlweDimension, LweDimension, PolynomialSize,
@@ -13,11 +14,27 @@ use tfhe::shortint::{
 /// correctly work with string of length larger than 8 bits.
 pub const NUMBER_BLOCKS: usize = 4;
 
-#[derive(Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 /// Encrypted ascii character are RadixCiphertext encryption of the corresponding integer
 pub struct FheAsciiChar(pub RadixCiphertext);
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+impl FheAsciiChar {
+    /// Returns the value of the character if it is a trivial encryption, as produced by
+    /// `server_encrypt_str` or `add_clear`. Such characters are compared and selected in the clear.
+    pub fn clear_value(&self) -> Option<u8> {
+        self.0.decrypt_trivial::<u8>().ok()
+    }
+
+    /// Returns the character as a `ClearOrEncryptedChar`, clear if it is a trivial encryption.
+    pub fn to_clear_or_encrypted(&self) -> ClearOrEncryptedChar {
+        match self.clear_value() {
+            Some(clear_char) => ClearOrEncryptedChar::Clear(clear_char),
+            None => ClearOrEncryptedChar::Encrypted(self.clone()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
 /// Padding zeros are allowed anywhere in the content of an FheString, they are ignored after
 /// decryption. They allow to obfuscate the string length.
 pub enum Padding {
@@ -43,7 +60,7 @@ impl PartialOrd for Padding {
     }
 }
 
-#[derive(Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub enum ClearOrEncrypted<T, U> {
     Clear(T),
     Encrypted(U),
@@ -54,7 +71,7 @@ pub enum ClearOrEncrypted<T, U> {
 pub type FheStrLength = ClearOrEncrypted<usize, RadixCiphertext>;
 pub type ClearOrEncryptedChar = ClearOrEncrypted<u8, FheAsciiChar>;
 
-#[derive(Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 /// The main type to store an encrypted string.
 /// Its content is a vector of FheAsciiChar, eventually containing some padding zeros, ignored after
 /// decryption. The location of padding zeros is indicated by `padding`. The length of the string
@@ -72,6 +89,34 @@ impl FheString {
     }
 }
 
+/// Number of characters packed in each word of an `FhePackedString`. A word is a RadixCiphertext of
+/// `CHARS_PER_WORD * NUMBER_BLOCKS` blocks, so 64 bits for 8 characters.
+pub const CHARS_PER_WORD: usize = 8;
+
+#[derive(Clone)]
+/// An encrypted string packing `CHARS_PER_WORD` characters in each RadixCiphertext of `words`, the
+/// first character of a word being the most significant. Padding zeros are only allowed at the
+/// end, so that equal strings have equal words and comparisons can be done word by word.
+pub struct FhePackedString {
+    pub words: Vec<RadixCiphertext>,
+    pub length: FheStrLength,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+/// An encrypted string of arbitrary bytes. Contrary to `FheString`, null bytes are part of the
+/// string, so padding cannot be marked by zeros. The bytes of the string are the `length` first
+/// elements of `content`, the following elements are padding and are ignored after decryption.
+pub struct FheBytes {
+    pub content: Vec<FheAsciiChar>,
+    pub length: FheStrLength,
+}
+
+impl FheBytes {
+    pub fn len(&self) -> &FheStrLength {
+        &self.length
+    }
+}
+
 /// Non secure cryptographic parameters to allow fast tests.
 pub const PARAM_MESSAGE_2_CARRY_2_TEST: ClassicPBSParameters = ClassicPBSParameters {
     lwe_dimension: LweDimension(1),
@@ -99,7 +144,7 @@ pub fn gen_keys_test() -> (StringClientKey, StringServerKey) {
             integer_key: radix_client_key,
         },
         StringServerKey {
-            integer_key: server_key,
+            integer_key: server_key.into(),
         },
     )
 }
@@ -120,7 +165,7 @@ pub fn gen_keys_number_blocks(num_blocks: usize) -> (StringClientKey, StringServ
             integer_key: radix_client_key,
         },
         StringServerKey {
-            integer_key: server_key,
+            integer_key: server_key.into(),
         },
     )
 }
@@ -0,0 +1,337 @@
+use crate::ciphertext::{gen_keys, gen_keys_test, FheString};
+use crate::client_key::StringClientKey;
+use crate::server_key::split::FheSplit;
+use crate::server_key::StringServerKey;
+use clap::{Subcommand, ValueEnum};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// Subcommands running each step of the two party workflow: the client generates the keys,
+/// encrypts its strings and decrypts the results, the server evaluates operations on encrypted
+/// strings with the server key only. Keys and ciphertexts are exchanged as bincode files.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generates a client key and a server key and writes them to files.
+    Keygen {
+        #[arg(long, default_value = "client_key.bin")]
+        client_key: PathBuf,
+
+        #[arg(long, default_value = "server_key.bin")]
+        server_key: PathBuf,
+
+        /// Uses non secure cryptographic parameters, for fast tests only.
+        #[arg(long)]
+        test_parameters: bool,
+    },
+
+    /// Encrypts a string with the client key and writes the encrypted string to a file.
+    Encrypt {
+        #[arg(long, default_value = "client_key.bin")]
+        client_key: PathBuf,
+
+        /// Clear string to encrypt.
+        input: String,
+
+        /// File to write the encrypted string to.
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Number of padding zeros to add at the end of the string.
+        #[arg(short, long, default_value_t = 0)]
+        padding_zeros: usize,
+    },
+
+    /// Evaluates an operation on encrypted strings with the server key and writes the encrypted
+    /// result to a file.
+    Eval {
+        #[arg(long, default_value = "server_key.bin")]
+        server_key: PathBuf,
+
+        operation: Operation,
+
+        /// Files of the encrypted string arguments, in the order of the arguments of the operation.
+        inputs: Vec<PathBuf>,
+
+        /// File to write the encrypted result to.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Decrypts an encrypted string or an encrypted result with the client key and prints it.
+    Decrypt {
+        #[arg(long, default_value = "client_key.bin")]
+        client_key: PathBuf,
+
+        /// File of the encrypted value to decrypt.
+        input: PathBuf,
+    },
+}
+
+/// Operations that can be evaluated by the `eval` subcommand. The first argument is the string to
+/// process, the other ones are patterns.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Operation {
+    Len,
+    ToLowercase,
+    ToUppercase,
+    Trim,
+    Add,
+    Eq,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Find,
+    Rfind,
+    StripPrefix,
+    Split,
+    Rsplit,
+    Replace,
+}
+
+impl Operation {
+    /// Number of encrypted strings taken as arguments.
+    fn number_arguments(&self) -> usize {
+        match self {
+            Operation::Len | Operation::ToLowercase | Operation::ToUppercase | Operation::Trim => 1,
+            Operation::Replace => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// Encrypted values exchanged between the client and the server, tagged with their type so that
+/// the client knows how to decrypt a result.
+#[derive(Serialize, Deserialize)]
+pub enum FheValue {
+    String(FheString),
+    Bool(BooleanBlock),
+    Integer(RadixCiphertext),
+    OptionInt(BooleanBlock, RadixCiphertext),
+    OptionString(BooleanBlock, FheString),
+    Split(FheSplit),
+}
+
+/// Runs a subcommand.
+pub fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Keygen {
+            client_key,
+            server_key,
+            test_parameters,
+        } => {
+            let (generated_client_key, generated_server_key) = match test_parameters {
+                true => gen_keys_test(),
+                false => gen_keys(),
+            };
+            write_to_file(&client_key, &generated_client_key)?;
+            write_to_file(&server_key, &generated_server_key)?;
+        }
+        Command::Encrypt {
+            client_key,
+            input,
+            output,
+            padding_zeros,
+        } => {
+            let client_key: StringClientKey = read_from_file(&client_key)?;
+            let encrypted_input = client_key
+                .encrypt_str_padding(&input, padding_zeros)
+                .map_err(|e| format!("cannot encrypt {:?}: {:?}", input, e))?;
+            write_to_file(&output, &FheValue::String(encrypted_input))?;
+        }
+        Command::Eval {
+            server_key,
+            operation,
+            inputs,
+            output,
+        } => {
+            let server_key: StringServerKey = read_from_file(&server_key)?;
+            let arguments = inputs
+                .iter()
+                .map(|path| match read_from_file(path)? {
+                    FheValue::String(s) => Ok(s),
+                    _ => Err(format!("{} is not an encrypted string", path.display()).into()),
+                })
+                .collect::<Result<Vec<FheString>, Box<dyn Error>>>()?;
+            write_to_file(&output, &eval(&server_key, operation, &arguments)?)?;
+        }
+        Command::Decrypt { client_key, input } => {
+            let client_key: StringClientKey = read_from_file(&client_key)?;
+            println!("{}", decrypt(&client_key, &read_from_file(&input)?)?);
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates `operation` on the encrypted `arguments`, using only the server key.
+pub fn eval(
+    server_key: &StringServerKey,
+    operation: Operation,
+    arguments: &[FheString],
+) -> Result<FheValue, Box<dyn Error>> {
+    if arguments.len() != operation.number_arguments() {
+        return Err(format!(
+            "{:?} takes {} encrypted strings, got {}",
+            operation,
+            operation.number_arguments(),
+            arguments.len()
+        )
+        .into());
+    }
+    let s = &arguments[0];
+    Ok(match operation {
+        Operation::Len => {
+            FheValue::Integer(server_key.add_length_to_radix(&server_key.create_zero(), &s.length))
+        }
+        Operation::ToLowercase => FheValue::String(server_key.to_lowercase(s)),
+        Operation::ToUppercase => FheValue::String(server_key.to_uppercase(s)),
+        Operation::Trim => FheValue::String(server_key.trim(s)),
+        Operation::Add => FheValue::String(server_key.add(s.clone(), &arguments[1])),
+        Operation::Eq => FheValue::Bool(server_key.eq(s, &arguments[1])),
+        Operation::Contains => FheValue::Bool(server_key.contains(s, &arguments[1])),
+        Operation::StartsWith => FheValue::Bool(server_key.starts_with(s, &arguments[1])),
+        Operation::EndsWith => FheValue::Bool(server_key.ends_with(s, &arguments[1])),
+        Operation::Find => {
+            let (found, index) = server_key.find(s, &arguments[1]);
+            FheValue::OptionInt(found, index)
+        }
+        Operation::Rfind => {
+            let (found, index) = server_key.rfind(s, &arguments[1]);
+            FheValue::OptionInt(found, index)
+        }
+        Operation::StripPrefix => {
+            let (stripped, result) = server_key.strip_prefix(s, &arguments[1]);
+            FheValue::OptionString(stripped, result)
+        }
+        Operation::Split => FheValue::Split(server_key.split(s, &arguments[1])),
+        Operation::Rsplit => FheValue::Split(server_key.rsplit(s, &arguments[1])),
+        Operation::Replace => FheValue::String(server_key.replace(s, &arguments[1], &arguments[2])),
+    })
+}
+
+/// Decrypts an encrypted value with the client key and formats it like the corresponding value
+/// of the standard library would be printed.
+pub fn decrypt(client_key: &StringClientKey, value: &FheValue) -> Result<String, Box<dyn Error>> {
+    Ok(match value {
+        FheValue::String(s) => format!("{:?}", client_key.decrypt_string(s)?),
+        FheValue::Bool(b) => format!("{}", client_key.decrypt_bool(b)),
+        FheValue::Integer(n) => format!("{}", client_key.decrypt_integer(n)),
+        FheValue::OptionInt(found, index) => format!(
+            "{:?}",
+            client_key
+                .decrypt_bool(found)
+                .then(|| client_key.decrypt_integer(index))
+        ),
+        FheValue::OptionString(found, s) => match client_key.decrypt_bool(found) {
+            true => format!("Some({:?})", client_key.decrypt_string(s)?),
+            false => String::from("None"),
+        },
+        FheValue::Split(split) => {
+            let number_parts = client_key.decrypt_integer(&split.number_parts) as usize;
+            if number_parts > split.parts.len() {
+                return Err(format!(
+                    "invalid split: {} parts announced but only {} stored",
+                    number_parts,
+                    split.parts.len()
+                )
+                .into());
+            }
+            let parts = split.parts[..number_parts]
+                .iter()
+                .map(|s| client_key.decrypt_string(s))
+                .collect::<Result<Vec<String>, _>>()?;
+            format!("{:?}", parts)
+        }
+    })
+}
+
+fn write_to_file(path: &Path, value: &impl Serialize) -> Result<(), Box<dyn Error>> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("cannot create {}: {}", path.display(), e))?;
+    bincode::serialize_into(std::io::BufWriter::new(file), value)?;
+    Ok(())
+}
+
+fn read_from_file<T: DeserializeOwned>(path: &Path) -> Result<T, Box<dyn Error>> {
+    let file =
+        std::fs::File::open(path).map_err(|e| format!("cannot open {}: {}", path.display(), e))?;
+    Ok(bincode::deserialize_from(std::io::BufReader::new(file))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, read_from_file, run, Command, FheValue, Operation};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::split::FheSplit;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_cli_workflow() {
+        let directory =
+            std::env::temp_dir().join(format!("fhe_strings_cli_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let file = |name: &str| directory.join(name);
+
+        run(Command::Keygen {
+            client_key: file("client_key.bin"),
+            server_key: file("server_key.bin"),
+            test_parameters: true,
+        })
+        .unwrap();
+        for (input, name, padding_zeros) in [("a,b,,c", "s.bin", 2), (",", "pattern.bin", 0)] {
+            run(Command::Encrypt {
+                client_key: file("client_key.bin"),
+                input: input.to_string(),
+                output: file(name),
+                padding_zeros,
+            })
+            .unwrap();
+        }
+
+        let client_key: StringClientKey = read_from_file(&file("client_key.bin")).unwrap();
+        let eval_and_decrypt = |operation: Operation, inputs: Vec<PathBuf>| {
+            run(Command::Eval {
+                server_key: file("server_key.bin"),
+                operation,
+                inputs,
+                output: file("result.bin"),
+            })
+            .unwrap();
+            decrypt(&client_key, &read_from_file(&file("result.bin")).unwrap()).unwrap()
+        };
+        let s_and_pattern = vec![file("s.bin"), file("pattern.bin")];
+        assert_eq!(
+            eval_and_decrypt(Operation::Find, s_and_pattern.clone()),
+            "Some(1)"
+        );
+        assert_eq!(
+            eval_and_decrypt(Operation::Split, s_and_pattern.clone()),
+            r#"["a", "b", "", "c"]"#
+        );
+        assert_eq!(
+            eval_and_decrypt(
+                Operation::Replace,
+                vec![file("s.bin"), file("pattern.bin"), file("pattern.bin")]
+            ),
+            r#""a,b,,c""#
+        );
+        assert!(run(Command::Eval {
+            server_key: file("server_key.bin"),
+            operation: Operation::Replace,
+            inputs: s_and_pattern,
+            output: file("result.bin"),
+        })
+        .is_err());
+
+        // A split announcing more parts than it stores is rejected instead of panicking.
+        let tampered_split = FheValue::Split(FheSplit {
+            parts: vec![],
+            number_parts: client_key.encrypt_integer(1u8),
+            current_index: 0,
+        });
+        assert!(decrypt(&client_key, &tampered_split).is_err());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -1,10 +1,11 @@
-use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::ciphertext::{FheAsciiChar, FheBytes, FheStrLength, FheString, Padding};
+use crate::server_key::comparisons::FheOrdering;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::string::FromUtf8Error;
 use tfhe::core_crypto::prelude::UnsignedNumeric;
 use tfhe::integer::block_decomposition::DecomposableInto;
-use tfhe::integer::{RadixCiphertext, RadixClientKey};
+use tfhe::integer::{BooleanBlock, RadixCiphertext, RadixClientKey};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StringClientKey {
@@ -105,6 +106,41 @@ impl StringClientKey {
         FheAsciiChar(self.integer_key.encrypt(ascii_char))
     }
 
+    /// Encrypt a slice of arbitrary bytes.
+    pub fn encrypt_bytes(&self, bytes: &[u8]) -> FheBytes {
+        FheBytes {
+            content: bytes
+                .iter()
+                .map(|byte| self.encrypt_ascii_char(*byte))
+                .collect(),
+            length: FheStrLength::Clear(bytes.len()),
+        }
+    }
+
+    /// Encrypt a slice of arbitrary bytes, add `padding_size` encrypted padding bytes at the end
+    /// and encrypt the length.
+    pub fn encrypt_bytes_padding(&self, bytes: &[u8], padding_size: usize) -> FheBytes {
+        FheBytes {
+            content: StringClientKey::null_padded_vec_from_bytes(bytes, padding_size)
+                .iter()
+                .map(|byte| self.encrypt_ascii_char(*byte))
+                .collect(),
+            length: FheStrLength::Encrypted(self.integer_key.encrypt(bytes.len() as u64)),
+        }
+    }
+
+    /// Decrypt a byte string, padding bytes are ignored.
+    pub fn decrypt_bytes(&self, bytes: &FheBytes) -> Vec<u8> {
+        let length = match &bytes.length {
+            FheStrLength::Clear(length) => *length,
+            FheStrLength::Encrypted(length) => self.decrypt_integer(length) as usize,
+        };
+        bytes.content[..length]
+            .iter()
+            .map(|encrypted_byte| self.decrypt_ascii_char(encrypted_byte))
+            .collect()
+    }
+
     pub fn decrypt_ascii_char(&self, encrypted_char: &FheAsciiChar) -> u8 {
         self.integer_key.decrypt::<u8>(&encrypted_char.0)
     }
@@ -113,6 +149,20 @@ impl StringClientKey {
         self.integer_key.decrypt::<u32>(encrypted_int)
     }
 
+    pub fn decrypt_bool(&self, encrypted_bool: &BooleanBlock) -> bool {
+        self.integer_key.decrypt_bool(encrypted_bool)
+    }
+
+    pub fn decrypt_ordering(&self, encrypted_ordering: &FheOrdering) -> std::cmp::Ordering {
+        if self.integer_key.decrypt_bool(&encrypted_ordering.less) {
+            std::cmp::Ordering::Less
+        } else if self.integer_key.decrypt_bool(&encrypted_ordering.equal) {
+            std::cmp::Ordering::Equal
+        } else {
+            std::cmp::Ordering::Greater
+        }
+    }
+
     pub fn encrypt_integer<T: DecomposableInto<u64> + UnsignedNumeric>(
         &self,
         n: T,
@@ -133,6 +183,12 @@ impl StringClientKey {
         result.append(&mut vec![0; padding_size]);
         result
     }
+    /// Produce a `Vec<u8>` from a `&[u8]` by adding `padding_size` trailing `0`
+    pub fn null_padded_vec_from_bytes(bytes: &[u8], padding_size: usize) -> Vec<u8> {
+        let mut result: Vec<u8> = bytes.to_vec();
+        result.append(&mut vec![0; padding_size]);
+        result
+    }
     /// Create a vector with padding in `padding_size` zeros in random position in between the
     /// characters of s.
     pub fn randomly_null_padded_vec_from_str(s: &str, padding_size: usize) -> Vec<u8> {
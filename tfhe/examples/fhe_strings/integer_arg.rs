@@ -28,6 +28,10 @@ pub trait FheIntegerArg {
     //fn repeat_string(&self, server_key: &StringServerKey, s: &FheString) -> FheString;
     fn add_one(&self, server_key: &StringServerKey) -> Self;
     fn to_string(&self) -> String;
+    /// Encrypts self on the number of bits of the lengths of strings. Clear values which do not
+    /// fit are clamped to the greatest one.
+    fn to_radix(&self, server_key: &StringServerKey) -> RadixCiphertext;
+    fn clear_value(&self) -> Option<usize>;
 }
 
 macro_rules! impl_integer_arg_method {
@@ -65,6 +69,12 @@ impl FheIntegerArg for u32 {
     fn to_string(&self) -> String {
         "clear".to_string()
     }
+    fn to_radix(&self, server_key: &StringServerKey) -> RadixCiphertext {
+        server_key.create_n(u8::try_from(*self).unwrap_or(u8::MAX))
+    }
+    fn clear_value(&self) -> Option<usize> {
+        Some(*self as usize)
+    }
 }
 
 impl FheIntegerArg for usize {
@@ -75,6 +85,12 @@ impl FheIntegerArg for usize {
     fn to_string(&self) -> String {
         "clear".to_string()
     }
+    fn to_radix(&self, server_key: &StringServerKey) -> RadixCiphertext {
+        server_key.create_n(u8::try_from(*self).unwrap_or(u8::MAX))
+    }
+    fn clear_value(&self) -> Option<usize> {
+        Some(*self)
+    }
 }
 
 impl FheIntegerArg for RadixCiphertext {
@@ -85,4 +101,10 @@ impl FheIntegerArg for RadixCiphertext {
     fn to_string(&self) -> String {
         "encrypted".to_string()
     }
+    fn to_radix(&self, _server_key: &StringServerKey) -> RadixCiphertext {
+        self.clone()
+    }
+    fn clear_value(&self) -> Option<usize> {
+        None
+    }
 }
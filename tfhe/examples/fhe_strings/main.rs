@@ -1,4 +1,5 @@
 mod ciphertext;
+mod cli;
 mod client_key;
 mod integer_arg;
 mod pattern;
@@ -6,6 +7,7 @@ mod server_key;
 mod test_generating_macros;
 mod timing_macros;
 mod timing_pair_strings_macros;
+mod timing_report;
 
 use crate::ciphertext::{gen_keys, gen_keys_test, FheStrLength, FheString};
 use crate::client_key::StringClientKey;
@@ -15,9 +17,16 @@ use clap::Parser;
 use lazy_static::lazy_static;
 use tfhe::integer::RadixCiphertext;
 use timing_pair_strings_macros::{padding_to_string, Encryption};
+use timing_report::OutputFormat;
 
 #[derive(Parser, Debug)]
-#[command(about, long_about, verbatim_doc_comment)]
+#[command(
+    about,
+    long_about,
+    verbatim_doc_comment,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 /// Program to time FHE functions acting on encrypted strings and compare the result with the
 /// standard library.
 ///
@@ -31,14 +40,22 @@ use timing_pair_strings_macros::{padding_to_string, Encryption};
 ///
 /// - Provide an integer arg with the flag -p to indicate the number of padding zeros to add to
 ///   string arguments.
+///
+/// - Alternatively, use the subcommands `keygen`, `encrypt`, `eval` and `decrypt` to run the client
+///   and the server sides separately, exchanging keys and ciphertexts through files.
 struct Arguments {
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+
     /// Input string to process, the program times FHE functions acting on it and compare the
     /// result with the standard library.
-    input_string: String,
+    #[arg(required = true)]
+    input_string: Option<String>,
 
     /// First pattern argument (string), calls `input_string.method(pattern)`, and the FHE
     /// equivalent for various methods.
-    pattern: String,
+    #[arg(required = true)]
+    pattern: Option<String>,
 
     /// Optional pattern argument (char),  an additional character argument to time methods that
     /// also accept character patterns.
@@ -60,6 +77,11 @@ struct Arguments {
     /// Number of padding zeros for string arguments
     #[clap(default_value_t = 0, short, long)]
     padding_zeros: usize,
+
+    /// Output format of the timings. The json and csv formats emit one record per timed function
+    /// call, with the number of integer operations performed by the server key.
+    #[arg(value_enum, default_value_t = OutputFormat::Text, long)]
+    format: OutputFormat,
 }
 
 lazy_static! {
@@ -71,8 +93,16 @@ lazy_static! {
 
 fn main() {
     let arguments = Arguments::parse();
-    let clear_s = arguments.input_string;
-    let clear_pattern = arguments.pattern.as_str();
+    if let Some(command) = arguments.command {
+        if let Err(error) = cli::run(command) {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    timing_report::set_output_format(arguments.format);
+    let clear_s = arguments.input_string.unwrap();
+    let clear_pattern = arguments.pattern.as_deref().unwrap();
     let padding_zeros = arguments.padding_zeros;
 
     let encrypted_s = CLIENT_KEY.encrypt_str(&clear_s).unwrap();
@@ -404,8 +434,9 @@ fn main() {
 
 /// Times repeat for a clear integer argument.
 fn time_repeat_clear(clear_s: &str, encrypted_s: &FheString, clear_n: usize, padding_zeros: usize) {
-    let start = std::time::Instant::now();
+    let start = timing_report::start_timing();
     let result = SERVER_KEY.repeat_clear(encrypted_s, clear_n);
+    start.record_pbs_count();
     let duration = start.elapsed();
 
     let string_padding_zeros_string = match padding_zeros {
@@ -413,19 +444,23 @@ fn time_repeat_clear(clear_s: &str, encrypted_s: &FheString, clear_n: usize, pad
         _ => format!("{} padding zeros", padding_zeros),
     };
 
-    println!("\n\n\n{: <35} {}", "function:", std::stringify!(repeat));
-    println!("arguments:");
-    println!("{: <35} {:?}", "  └ encrypted string", clear_s);
-    println!("    └ {}", string_padding_zeros_string);
-    println!("{: <35} {:?}", "  └ clear integer", clear_n);
-    println!("results:");
-    println!("{: <35} {:}", "  ├ std result:", clear_s.repeat(clear_n));
-    println!(
+    display_line!("\n\n\n{: <35} {}", "function:", std::stringify!(repeat));
+    timing_report::begin_record(std::stringify!(repeat));
+    display_line!("arguments:");
+    display_line!("{: <35} {:?}", "  └ encrypted string", clear_s);
+    timing_report::record_argument("encrypted string", &clear_s, padding_zeros);
+    display_line!("    └ {}", string_padding_zeros_string);
+    display_line!("{: <35} {:?}", "  └ clear integer", clear_n);
+    timing_report::record_argument("clear integer", &clear_n, 0);
+    display_line!("results:");
+    display_line!("{: <35} {:}", "  ├ std result:", clear_s.repeat(clear_n));
+    display_line!(
         "{: <35} {:}",
         "  └ FHE result:",
         CLIENT_KEY.decrypt_string(&result).unwrap()
     );
-    println!("time:                               {:?}", duration);
+    display_line!("time:                               {:?}", duration);
+    timing_report::end_record(duration);
 }
 
 // Identifier used to pattern match in macro definition.
@@ -448,8 +483,9 @@ fn time_repeat_encrypted(
 ) {
     return_clear();
     return_encrypted();
-    let start = std::time::Instant::now();
+    let start = timing_report::start_timing();
     let result = SERVER_KEY.repeat_encrypted(encrypted_s, encrypted_n, max_n);
+    start.record_pbs_count();
     let duration = start.elapsed();
 
     let string_padding_zeros_string = match padding_zeros {
@@ -457,17 +493,21 @@ fn time_repeat_encrypted(
         _ => format!("{} padding zeros", padding_zeros),
     };
 
-    println!("\n\n\n{: <35} {}", "function:", std::stringify!(repeat));
-    println!("arguments:");
-    println!("{: <35} {:?}", "  └ encrypted string", clear_s);
-    println!("    └ {}", string_padding_zeros_string);
-    println!("{: <35} {:?}", "  └ encrypted integer", clear_n);
-    println!("results:");
-    println!("{: <35} {:}", "  ├ std result:", clear_s.repeat(clear_n));
-    println!(
+    display_line!("\n\n\n{: <35} {}", "function:", std::stringify!(repeat));
+    timing_report::begin_record(std::stringify!(repeat));
+    display_line!("arguments:");
+    display_line!("{: <35} {:?}", "  └ encrypted string", clear_s);
+    timing_report::record_argument("encrypted string", &clear_s, padding_zeros);
+    display_line!("    └ {}", string_padding_zeros_string);
+    display_line!("{: <35} {:?}", "  └ encrypted integer", clear_n);
+    timing_report::record_argument("encrypted integer", &clear_n, 0);
+    display_line!("results:");
+    display_line!("{: <35} {:}", "  ├ std result:", clear_s.repeat(clear_n));
+    display_line!(
         "{: <35} {:}",
         "  └ FHE result:",
         CLIENT_KEY.decrypt_string(&result).unwrap()
     );
-    println!("time:                               {:?}", duration);
+    display_line!("time:                               {:?}", duration);
+    timing_report::end_record(duration);
 }
@@ -1,11 +1,11 @@
-use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::ciphertext::{FheAsciiChar, FheBytes, FheStrLength, FheString, Padding};
 use crate::integer_arg::FheIntegerArg;
 use crate::server_key::find::FheOptionInt;
 use crate::server_key::split::FheSplit;
 use crate::server_key::strip::FheOptionString;
 
 use crate::server_key::StringServerKey;
-use tfhe::integer::BooleanBlock;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
 
 /// Creates a method with 3 arguments `&self, server_key, s`, that just calls a specified method
 /// of `server_key`.
@@ -32,15 +32,36 @@ macro_rules! forward_splitn_to_server_key {
     };
 }
 
-/// A trait to work with patterns either character or string, clear or encrypted. Its purpose is to
-/// dispatch between various functions.
-pub trait FhePattern {
+/// The comparison of a pattern with a slice of characters, shared by the patterns of `FheString`
+/// and the patterns of `FheBytes`.
+pub trait FheSlicePattern {
+    /// Checks if the slice haystack starts with self, ignoring the padding of self. The content of
+    /// an `FheBytes` is not padded by zeros, so its length is checked separately.
     fn is_prefix_of_slice(
         &self,
         server_key: &StringServerKey,
         haystack: &[FheAsciiChar],
     ) -> BooleanBlock;
 
+    /// The length of self, clear for clear patterns.
+    fn pattern_length(&self) -> FheStrLength;
+
+    /// The length of self, encrypted on the same number of bits as the lengths of strings.
+    fn length_radix(&self, server_key: &StringServerKey) -> RadixCiphertext {
+        let length = self.pattern_length();
+        if let FheStrLength::Clear(clear_length) = length {
+            assert!(
+                clear_length <= u8::MAX as usize,
+                "the length of a pattern should be at most 255"
+            );
+        }
+        server_key.add_length_to_radix(&server_key.create_zero(), &length)
+    }
+}
+
+/// A trait to work with patterns either character or string, clear or encrypted. Its purpose is to
+/// dispatch between various functions.
+pub trait FhePattern: FheSlicePattern {
     fn is_prefix_of_string(
         &self,
         server_key: &StringServerKey,
@@ -79,6 +100,19 @@ pub trait FhePattern {
 
     fn rfind_in(&self, server_key: &StringServerKey, haystack: &FheString) -> FheOptionInt;
 
+    /// For each position of the content of haystack from `skip` on, and for the end of the
+    /// content, an encryption of true if self starts at this position.
+    fn prefix_matches_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+        skip: usize,
+    ) -> Vec<BooleanBlock> {
+        (skip..=haystack.content.len())
+            .map(|position| self.is_prefix_of_slice(server_key, &haystack.content[position..]))
+            .collect()
+    }
+
     fn split_string(&self, server_key: &StringServerKey, s: &FheString) -> FheSplit;
 
     fn split_inclusive_string(&self, server_key: &StringServerKey, s: &FheString) -> FheSplit;
@@ -103,6 +137,44 @@ pub trait FhePattern {
         s: &FheString,
     ) -> FheSplit;
 
+    fn find_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionInt;
+
+    fn rfind_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionInt;
+
+    fn is_contained_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock;
+
+    fn is_prefix_ignore_case_of_string(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock;
+
+    fn is_suffix_ignore_case_of_string(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock;
+
+    fn split_ignore_case_string(&self, server_key: &StringServerKey, s: &FheString) -> FheSplit;
+
+    fn strip_prefix_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionString;
+
     fn is_contained_in(&self, server_key: &StringServerKey, haystack: &FheString) -> BooleanBlock {
         let mut result = server_key.create_false();
         for i in 0..haystack.content.len() {
@@ -126,18 +198,15 @@ pub trait FhePattern {
     fn ge_string(&self, server_key: &StringServerKey, _s: &FheString) -> BooleanBlock {
         server_key.create_false()
     }
-}
-
-impl FhePattern for &str {
-    fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
-        let encrypted_self = server_key.server_encrypt_str(self).unwrap();
-        server_key.insert_in_fhe_split_result_padded_anywhere(fhe_split, &encrypted_self)
+    fn lt_string(&self, server_key: &StringServerKey, _s: &FheString) -> BooleanBlock {
+        server_key.create_false()
     }
-
-    fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
-        server_key.add_clear(s, self)
+    fn gt_string(&self, server_key: &StringServerKey, _s: &FheString) -> BooleanBlock {
+        server_key.create_false()
     }
+}
 
+impl FheSlicePattern for &[u8] {
     fn is_prefix_of_slice(
         &self,
         server_key: &StringServerKey,
@@ -147,18 +216,43 @@ impl FhePattern for &str {
         if self.len() > haystack.len() {
             return server_key.create_false();
         }
-        for (n, c) in haystack
-            .iter()
-            .enumerate()
-            .take(std::cmp::min(haystack.len(), self.len()))
-        {
+        for (c, byte) in haystack.iter().zip(self.iter()) {
             result = server_key
                 .integer_key
-                .boolean_bitand(&result, &server_key.eq_clear_char(c, self.as_bytes()[n]));
+                .boolean_bitand(&result, &server_key.eq_clear_char(c, *byte));
         }
         result
     }
 
+    fn pattern_length(&self) -> FheStrLength {
+        FheStrLength::Clear(self.len())
+    }
+}
+
+impl FheSlicePattern for &str {
+    fn is_prefix_of_slice(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &[FheAsciiChar],
+    ) -> BooleanBlock {
+        self.as_bytes().is_prefix_of_slice(server_key, haystack)
+    }
+
+    fn pattern_length(&self) -> FheStrLength {
+        FheStrLength::Clear(self.len())
+    }
+}
+
+impl FhePattern for &str {
+    fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
+        let encrypted_self = server_key.server_encrypt_str(self).unwrap();
+        server_key.insert_in_fhe_split_result_padded_anywhere(fhe_split, &encrypted_self)
+    }
+
+    fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
+        server_key.add_clear(s, self)
+    }
+
     fn is_prefix_of_string(
         &self,
         server_key: &StringServerKey,
@@ -213,14 +307,17 @@ impl FhePattern for &str {
     // }
 
     forward_to_server_key_method!(find_in, find_clear_string, FheOptionInt);
+
     forward_to_server_key_method!(rfind_in, rfind_clear_string, FheOptionInt);
     forward_to_server_key_method!(strip_prefix_in, strip_clear_prefix, FheOptionString);
     forward_to_server_key_method!(eq_string, eq_clear, BooleanBlock);
 
-    // The permutation le <-> ge is to coincides with the order of the arguments when calling
-    // methods
+    // The permutations le <-> ge and lt <-> gt are to coincide with the order of the arguments
+    // when calling methods
     forward_to_server_key_method!(le_string, ge_clear, BooleanBlock);
     forward_to_server_key_method!(ge_string, le_clear, BooleanBlock);
+    forward_to_server_key_method!(lt_string, gt_clear, BooleanBlock);
+    forward_to_server_key_method!(gt_string, lt_clear, BooleanBlock);
 
     forward_to_server_key_method!(eq_ignore_case_string, eq_ignore_case_clear, BooleanBlock);
     forward_to_server_key_method!(is_contained_in, contains_clear_string, BooleanBlock);
@@ -230,19 +327,43 @@ impl FhePattern for &str {
     forward_to_server_key_method!(rsplit_string, rsplit_clear, FheSplit);
     forward_to_server_key_method!(rsplit_terminator_string, rsplit_terminator_clear, FheSplit);
 
+    forward_to_server_key_method!(
+        find_ignore_case_in,
+        find_clear_string_ignore_case,
+        FheOptionInt
+    );
+    forward_to_server_key_method!(
+        rfind_ignore_case_in,
+        rfind_clear_string_ignore_case,
+        FheOptionInt
+    );
+    forward_to_server_key_method!(
+        is_contained_ignore_case_in,
+        contains_clear_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(
+        is_prefix_ignore_case_of_string,
+        starts_with_clear_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(
+        is_suffix_ignore_case_of_string,
+        ends_with_clear_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(split_ignore_case_string, split_clear_ignore_case, FheSplit);
+    forward_to_server_key_method!(
+        strip_prefix_ignore_case_in,
+        strip_clear_prefix_ignore_case,
+        FheOptionString
+    );
+
     forward_splitn_to_server_key!(splitn_string, splitn_clear_string);
     forward_splitn_to_server_key!(rsplitn_string, rsplitn_clear_string);
 }
 
-impl FhePattern for FheString {
-    fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
-        server_key.insert_in_fhe_split_result_padded_anywhere(fhe_split, self)
-    }
-
-    fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
-        server_key.add_encrypted(s, self)
-    }
-
+impl FheSlicePattern for FheString {
     fn is_prefix_of_slice(
         &self,
         server_key: &StringServerKey,
@@ -319,6 +440,33 @@ impl FhePattern for FheString {
         result
     }
 
+    fn pattern_length(&self) -> FheStrLength {
+        self.length.clone()
+    }
+}
+
+impl FhePattern for FheString {
+    fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
+        server_key.insert_in_fhe_split_result_padded_anywhere(fhe_split, self)
+    }
+
+    fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
+        server_key.add_encrypted(s, self)
+    }
+
+    fn prefix_matches_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+        skip: usize,
+    ) -> Vec<BooleanBlock> {
+        // The padding is pushed once here instead of at each position.
+        let pattern = server_key.push_padding_to_end(self);
+        (skip..=haystack.content.len())
+            .map(|position| pattern.is_prefix_of_slice(server_key, &haystack.content[position..]))
+            .collect()
+    }
+
     // fn eq_string(&self, server_key: &StringServerKey, s: &FheString) -> BooleanBlock {
     //     server_key.eq_encrypted(self, s)
     // }
@@ -378,10 +526,12 @@ impl FhePattern for FheString {
     forward_to_server_key_method!(strip_prefix_in, strip_encrypted_prefix, FheOptionString);
     forward_to_server_key_method!(eq_string, eq_encrypted, BooleanBlock);
 
-    // The permutation le <-> ge is to coincides with the order of the arguments when calling
-    // methods
+    // The permutations le <-> ge and lt <-> gt are to coincide with the order of the arguments
+    // when calling methods
     forward_to_server_key_method!(le_string, ge_encrypted, BooleanBlock);
     forward_to_server_key_method!(ge_string, le_encrypted, BooleanBlock);
+    forward_to_server_key_method!(lt_string, gt_encrypted, BooleanBlock);
+    forward_to_server_key_method!(gt_string, lt_encrypted, BooleanBlock);
 
     forward_to_server_key_method!(
         eq_ignore_case_string,
@@ -405,6 +555,34 @@ impl FhePattern for FheString {
         FheSplit
     );
 
+    forward_to_server_key_method!(find_ignore_case_in, find_string_ignore_case, FheOptionInt);
+    forward_to_server_key_method!(rfind_ignore_case_in, rfind_string_ignore_case, FheOptionInt);
+    forward_to_server_key_method!(
+        is_contained_ignore_case_in,
+        contains_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(
+        is_prefix_ignore_case_of_string,
+        starts_with_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(
+        is_suffix_ignore_case_of_string,
+        ends_with_string_ignore_case,
+        BooleanBlock
+    );
+    forward_to_server_key_method!(
+        split_ignore_case_string,
+        split_encrypted_ignore_case,
+        FheSplit
+    );
+    forward_to_server_key_method!(
+        strip_prefix_ignore_case_in,
+        strip_encrypted_prefix_ignore_case,
+        FheOptionString
+    );
+
     forward_splitn_to_server_key!(splitn_string, splitn_encrypted_string);
     forward_splitn_to_server_key!(rsplitn_string, rsplitn_encrypted_string);
 }
@@ -412,10 +590,15 @@ impl FhePattern for FheString {
 pub trait FheCharPattern {
     fn fhe_eq(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock;
 
+    fn fhe_eq_ignore_case(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock;
+
     fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString;
 
     fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString;
 
+    /// Returns the character as an `FheAsciiChar`, trivially encrypted if it is clear.
+    fn to_fhe_ascii_char(&self, server_key: &StringServerKey) -> FheAsciiChar;
+
     fn is_prefix_of_connected_string(
         &self,
         server_key: &StringServerKey,
@@ -460,6 +643,23 @@ pub trait FheCharPattern {
     }
 }
 
+impl<T: FheCharPattern> FheSlicePattern for T {
+    fn is_prefix_of_slice(
+        &self,
+        server_key: &StringServerKey,
+        haystack_slice: &[FheAsciiChar],
+    ) -> BooleanBlock {
+        if haystack_slice.is_empty() {
+            return server_key.create_false();
+        }
+        self.fhe_eq(server_key, &haystack_slice[0])
+    }
+
+    fn pattern_length(&self) -> FheStrLength {
+        FheStrLength::Clear(1)
+    }
+}
+
 impl<T: FheCharPattern> FhePattern for T {
     fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
         FheCharPattern::insert_in(self, server_key, fhe_split)
@@ -473,17 +673,6 @@ impl<T: FheCharPattern> FhePattern for T {
         FheCharPattern::push_to(self, server_key, s)
     }
 
-    fn is_prefix_of_slice(
-        &self,
-        server_key: &StringServerKey,
-        haystack_slice: &[FheAsciiChar],
-    ) -> BooleanBlock {
-        if haystack_slice.is_empty() {
-            return server_key.create_false();
-        }
-        self.fhe_eq(server_key, &haystack_slice[0])
-    }
-
     fn is_prefix_of_string(
         &self,
         server_key: &StringServerKey,
@@ -503,12 +692,66 @@ impl<T: FheCharPattern> FhePattern for T {
     forward_to_server_key_method!(strip_prefix_in, strip_char_prefix, FheOptionString);
     forward_to_server_key_method!(find_in, find_char, FheOptionInt);
     forward_to_server_key_method!(rfind_in, rfind_char, FheOptionInt);
+
     forward_to_server_key_method!(split_string, split_char, FheSplit);
     forward_to_server_key_method!(rsplit_string, rsplit_char, FheSplit);
     forward_to_server_key_method!(split_inclusive_string, split_inclusive_char, FheSplit);
     forward_to_server_key_method!(split_terminator_string, split_terminator_char, FheSplit);
     forward_to_server_key_method!(rsplit_terminator_string, rsplit_terminator_char, FheSplit);
 
+    // Case insensitive searches for characters only change the comparison of characters.
+    fn find_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionInt {
+        server_key.find_char(haystack, &IgnoreCaseChar(self))
+    }
+
+    fn rfind_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionInt {
+        server_key.rfind_char(haystack, &IgnoreCaseChar(self))
+    }
+
+    fn is_contained_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock {
+        IgnoreCaseChar(self).is_contained_in(server_key, haystack)
+    }
+
+    fn is_prefix_ignore_case_of_string(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock {
+        IgnoreCaseChar(self).is_prefix_of_string(server_key, haystack)
+    }
+
+    fn is_suffix_ignore_case_of_string(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> BooleanBlock {
+        IgnoreCaseChar(self).is_suffix_of_string(server_key, haystack)
+    }
+
+    fn split_ignore_case_string(&self, server_key: &StringServerKey, s: &FheString) -> FheSplit {
+        server_key.split_char(s, &IgnoreCaseChar(self))
+    }
+
+    fn strip_prefix_ignore_case_in(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &FheString,
+    ) -> FheOptionString {
+        server_key.strip_char_prefix(haystack, &IgnoreCaseChar(self))
+    }
+
     forward_splitn_to_server_key!(splitn_string, splitn_char);
     forward_splitn_to_server_key!(rsplitn_string, rsplitn_char);
 }
@@ -518,6 +761,10 @@ impl FheCharPattern for char {
         server_key.eq_clear_char(c, *self as u8)
     }
 
+    fn fhe_eq_ignore_case(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock {
+        server_key.eq_clear_char_ignore_case(c, *self as u8)
+    }
+
     fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
         server_key.add_clear_char(s, *self)
     }
@@ -526,6 +773,10 @@ impl FheCharPattern for char {
         let encrypted_self = server_key.server_encrypt_ascii_char(*self);
         server_key.insert_char_in_fhe_split_result_padded_anywhere(fhe_split, &encrypted_self)
     }
+
+    fn to_fhe_ascii_char(&self, server_key: &StringServerKey) -> FheAsciiChar {
+        server_key.server_encrypt_ascii_char(*self)
+    }
 }
 
 impl FheCharPattern for FheAsciiChar {
@@ -533,6 +784,10 @@ impl FheCharPattern for FheAsciiChar {
         server_key.eq_char(c, self)
     }
 
+    fn fhe_eq_ignore_case(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock {
+        server_key.eq_char_ignore_case(c, self)
+    }
+
     fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
         server_key.add_encrypted_char(s, self)
     }
@@ -540,6 +795,88 @@ impl FheCharPattern for FheAsciiChar {
     fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
         server_key.insert_char_in_fhe_split_result_padded_anywhere(fhe_split, self)
     }
+
+    fn to_fhe_ascii_char(&self, _server_key: &StringServerKey) -> FheAsciiChar {
+        self.clone()
+    }
+}
+
+/// A character pattern (clear or encrypted) compared to the characters of a string without taking
+/// case into account.
+pub struct IgnoreCaseChar<'a, T: FheCharPattern>(pub &'a T);
+
+impl<'a, T: FheCharPattern> FheCharPattern for IgnoreCaseChar<'a, T> {
+    fn fhe_eq(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock {
+        self.0.fhe_eq_ignore_case(server_key, c)
+    }
+
+    fn fhe_eq_ignore_case(&self, server_key: &StringServerKey, c: &FheAsciiChar) -> BooleanBlock {
+        self.0.fhe_eq_ignore_case(server_key, c)
+    }
+
+    fn push_to(&self, server_key: &StringServerKey, s: FheString) -> FheString {
+        FheCharPattern::push_to(self.0, server_key, s)
+    }
+
+    fn insert_in(&self, server_key: &StringServerKey, fhe_split: &FheSplit) -> FheString {
+        FheCharPattern::insert_in(self.0, server_key, fhe_split)
+    }
+
+    fn to_fhe_ascii_char(&self, server_key: &StringServerKey) -> FheAsciiChar {
+        self.0.to_fhe_ascii_char(server_key)
+    }
+}
+
+impl FheSlicePattern for u8 {
+    fn is_prefix_of_slice(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &[FheAsciiChar],
+    ) -> BooleanBlock {
+        match haystack.first() {
+            Some(c) => server_key.eq_clear_char(c, *self),
+            None => server_key.create_false(),
+        }
+    }
+
+    fn pattern_length(&self) -> FheStrLength {
+        FheStrLength::Clear(1)
+    }
+}
+
+impl FheSlicePattern for FheBytes {
+    fn is_prefix_of_slice(
+        &self,
+        server_key: &StringServerKey,
+        haystack: &[FheAsciiChar],
+    ) -> BooleanBlock {
+        let mut result = server_key.create_true();
+        for (i, byte) in self.content.iter().enumerate() {
+            let byte_matches = match haystack.get(i) {
+                Some(c) => server_key.eq_char(c, byte),
+                None => server_key.create_false(),
+            };
+            // Bytes of the pattern after its length are padding and always match.
+            let byte_ok = match &self.length {
+                FheStrLength::Clear(length) if i >= *length => break,
+                FheStrLength::Clear(_) => byte_matches,
+                FheStrLength::Encrypted(length) => server_key.integer_key.boolean_bitor(
+                    &server_key
+                        .integer_key
+                        .scalar_le_parallelized(length, i as u64),
+                    &byte_matches,
+                ),
+            };
+            server_key
+                .integer_key
+                .boolean_bitand_assign(&mut result, &byte_ok);
+        }
+        result
+    }
+
+    fn pattern_length(&self) -> FheStrLength {
+        self.length.clone()
+    }
 }
 
 // #[test]
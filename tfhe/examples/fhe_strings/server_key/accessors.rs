@@ -29,7 +29,7 @@ impl StringServerKey {
     /// character if `n` is out of range.
     fn nth_clear_padding_anywhere(&self, s: &FheString, n: usize) -> FheAsciiChar {
         let mut current_index: RadixCiphertext = self.create_zero();
-        let mut result = self.create_zero();
+        let mut result = FheAsciiChar(self.create_zero());
         for c in &s.content {
             // Check if the right index `n` is reached.
             let right_index = self
@@ -37,19 +37,16 @@ impl StringServerKey {
                 .scalar_eq_parallelized(&current_index, n as u64);
 
             // Update result when index `n` is reached.
-            result = self
-                .integer_key
-                .cmux_parallelized(&right_index, &c.0, &result);
+            result = self.cmux_char(&right_index, c, &result);
 
             // Increment `current_index` if the current char is non null
-            let current_char_non_null: BooleanBlock =
-                self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let current_char_non_null: BooleanBlock = self.ne_clear_char(c, 0);
             self.integer_key.add_assign_parallelized(
                 &mut current_index,
                 &self.bool_to_radix(&current_char_non_null),
             );
         }
-        FheAsciiChar(result)
+        result
     }
 
     /// Returns the `n`-th character of `s` for `n` an encrypted index and `s` an encrypted string
@@ -61,7 +58,7 @@ impl StringServerKey {
         encrypted_n: &RadixCiphertext,
     ) -> FheAsciiChar {
         let mut current_index: RadixCiphertext = self.create_zero();
-        let mut result = self.create_zero();
+        let mut result = FheAsciiChar(self.create_zero());
         for c in &s.content {
             // Check if the right index `n` is reached.
             let right_index = self
@@ -69,19 +66,16 @@ impl StringServerKey {
                 .eq_parallelized(&current_index, encrypted_n);
 
             // Update result when index `n` is reached.
-            result = self
-                .integer_key
-                .cmux_parallelized(&right_index, &c.0, &result);
+            result = self.cmux_char(&right_index, c, &result);
 
             // Increment `current_index` if the current char is non null
-            let current_char_non_null: BooleanBlock =
-                self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let current_char_non_null: BooleanBlock = self.ne_clear_char(c, 0);
             self.integer_key.add_assign_parallelized(
                 &mut current_index,
                 &self.bool_to_radix(&current_char_non_null),
             );
         }
-        FheAsciiChar(result)
+        result
     }
 
     /// Returns the `n`-th character of `s` for `n` an encrypted index and `s` an encrypted string
@@ -92,7 +86,7 @@ impl StringServerKey {
         s: &FheString,
         encrypted_n: &RadixCiphertext,
     ) -> FheAsciiChar {
-        let mut result = self.create_zero();
+        let mut result = FheAsciiChar(self.create_zero());
         for (i, c) in s.content.iter().enumerate() {
             // Check if the right index `n` is reached.
             let right_index = self
@@ -100,11 +94,9 @@ impl StringServerKey {
                 .scalar_eq_parallelized(encrypted_n, i as u64);
 
             // Update result when index `n` is reached.
-            result = self
-                .integer_key
-                .cmux_parallelized(&right_index, &c.0, &result)
+            result = self.cmux_char(&right_index, c, &result)
         }
-        FheAsciiChar(result)
+        result
     }
 
     /// If `condition` is an encryption of `1` (for `true`), this function returns an `FheString`
@@ -113,11 +105,9 @@ impl StringServerKey {
     pub fn cmux_empty_string(&self, condition: &BooleanBlock, if_string: &FheString) -> FheString {
         let radix_condition = self.bool_to_radix(condition);
         let mut content_result: Vec<FheAsciiChar> = Vec::with_capacity(if_string.content.len());
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
         for c in if_string.content.iter() {
-            content_result.push(FheAsciiChar(
-                self.integer_key.cmux_parallelized(condition, &c.0, &zero),
-            ));
+            content_result.push(self.cmux_char(condition, c, &zero));
         }
         let encrypted_length_result = match if_string.len() {
             FheStrLength::Clear(clear_length) => self
@@ -137,4 +127,111 @@ impl StringServerKey {
             padding: padding_result,
         }
     }
+
+    /// Returns a copy of `s` with its padding pushed to the end and with its content extended by
+    /// padding zeros up to `content_length`. The length of the result is encrypted.
+    pub fn pad_to_content_length(&self, s: &FheString, content_length: usize) -> FheString {
+        let mut result = self.push_padding_to_end(s);
+        while result.content.len() < content_length {
+            result.content.push(FheAsciiChar(self.create_zero()));
+        }
+        result.padding = Padding::Final;
+        result.length =
+            FheStrLength::Encrypted(self.add_length_to_radix(&self.create_zero(), &s.length));
+        result
+    }
+
+    /// Returns `s_true` if `condition` encrypts true and `s_false` otherwise. Assumes that both
+    /// strings have final padding, the same content length and encrypted lengths.
+    pub fn cmux_same_content_length(
+        &self,
+        condition: &BooleanBlock,
+        s_true: &FheString,
+        s_false: &FheString,
+    ) -> FheString {
+        let content = s_true
+            .content
+            .iter()
+            .zip(&s_false.content)
+            .map(|(c_true, c_false)| self.cmux_char(condition, c_true, c_false))
+            .collect();
+        let length = match (&s_true.length, &s_false.length) {
+            (FheStrLength::Encrypted(l_true), FheStrLength::Encrypted(l_false)) => {
+                FheStrLength::Encrypted(
+                    self.integer_key
+                        .cmux_parallelized(condition, l_true, l_false),
+                )
+            }
+            (l_true, l_false) => FheStrLength::Encrypted(self.integer_key.cmux_parallelized(
+                condition,
+                &self.add_length_to_radix(&self.create_zero(), l_true),
+                &self.add_length_to_radix(&self.create_zero(), l_false),
+            )),
+        };
+        FheString {
+            content,
+            padding: Padding::Final,
+            length,
+        }
+    }
+
+    /// Returns an encryption of `s_true` if `condition` encrypts true and an encryption of
+    /// `s_false` otherwise. The strings can have different content lengths, paddings and clear or
+    /// encrypted lengths. The result has final padding, an encrypted length and the content length
+    /// of the longest of the two strings.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let field = client_key.encrypt_str("secret").unwrap();
+    /// let masked = server_key.server_encrypt_str("***").unwrap();
+    /// let flag = server_key.create_true();
+    /// let result = server_key.select(&flag, &masked, &field);
+    /// assert_eq!(client_key.decrypt_string(&result).unwrap(), "***");
+    /// ```
+    pub fn select(
+        &self,
+        condition: &BooleanBlock,
+        s_true: &FheString,
+        s_false: &FheString,
+    ) -> FheString {
+        let content_length = std::cmp::max(s_true.content.len(), s_false.content.len());
+        self.cmux_same_content_length(
+            condition,
+            &self.pad_to_content_length(s_true, content_length),
+            &self.pad_to_content_length(s_false, content_length),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{gen_keys_test, FheStrLength};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_select() {
+        let s_true = CLIENT_KEY.encrypt_str_random_padding("ab", 2).unwrap();
+        let s_false = CLIENT_KEY.encrypt_str("cdefg").unwrap();
+        for condition in [true, false] {
+            let encrypted_condition =
+                SERVER_KEY.radix_to_bool(&CLIENT_KEY.encrypt_integer(condition as u8));
+            let result = SERVER_KEY.select(&encrypted_condition, &s_true, &s_false);
+            let expected = if condition { "ab" } else { "cdefg" };
+            assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), expected);
+            let length = match result.len() {
+                FheStrLength::Encrypted(length) => CLIENT_KEY.decrypt_integer(length) as usize,
+                FheStrLength::Clear(length) => *length,
+            };
+            assert_eq!(length, expected.len());
+        }
+    }
 }
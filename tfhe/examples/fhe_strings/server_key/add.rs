@@ -137,4 +137,33 @@ mod tests {
     //     let decrypted_str = CLIENT_KEY.decrypt_string(&add_str1_str2).unwrap();
     //     assert_eq!(decrypted_str, "abbc");
     // }
+
+    #[test]
+    fn test_hybrid_string() {
+        let name = CLIENT_KEY.encrypt_str_random_padding("Bob", 1).unwrap();
+        let template = SERVER_KEY.server_encrypt_str("Dear ").unwrap();
+        let hybrid = SERVER_KEY.add_clear(SERVER_KEY.add(template, &name), ", hi");
+        assert_eq!(hybrid.content[0].clear_value(), Some(b'D'));
+        assert_eq!(hybrid.content[5].clear_value(), None);
+
+        let upper = SERVER_KEY.to_uppercase(&hybrid);
+        assert_eq!(upper.content[1].clear_value(), Some(b'E'));
+        assert_eq!(CLIENT_KEY.decrypt_string(&upper).unwrap(), "DEAR BOB, HI");
+
+        let eq = SERVER_KEY.eq(&hybrid, &"Dear Bob, hi");
+        assert!(CLIENT_KEY.decrypt_bool(&eq));
+        let (found, index) = SERVER_KEY.find(&hybrid, &"ob,");
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 6);
+        let split = SERVER_KEY.split(&hybrid, &' ');
+        assert_eq!(CLIENT_KEY.decrypt_integer(&split.number_parts), 3);
+
+        // Comparisons and selections of clear characters give trivial encryptions.
+        let is_d = SERVER_KEY.eq_clear_char(&hybrid.content[0], b'D');
+        assert_eq!(is_d.decrypt_trivial(), Ok(true));
+        let is_less = SERVER_KEY.lt_char(&hybrid.content[0], &hybrid.content[1]);
+        assert_eq!(is_less.decrypt_trivial(), Ok(true));
+        let selected = SERVER_KEY.cmux_char(&is_d, &hybrid.content[1], &hybrid.content[5]);
+        assert_eq!(selected.clear_value(), Some(b'e'));
+    }
 }
@@ -0,0 +1,159 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::pattern::FheCharPattern;
+use crate::server_key::StringServerKey;
+
+// Implement functions to align encrypted strings in a field of given width, like the `<`, `>` and
+// `^` alignments of `format!`.
+impl StringServerKey {
+    /// Aligns s to the right of a field of `width` characters, filling the start with `fill`, like
+    /// `format!("{:>width$}", s)`. If s fits in the field, the result has a clear length equal to
+    /// `width`, even if the length of s is encrypted.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str_random_padding("ab", 1).unwrap();
+    /// let result = server_key.pad_start(&encrypted_str, 5, &'.');
+    /// assert_eq!(client_key.decrypt_string(&result).unwrap(), "...ab");
+    /// ```
+    pub fn pad_start(&self, s: &FheString, width: usize, fill: &impl FheCharPattern) -> FheString {
+        self.align(s, width, fill, |fill_length| fill_length)
+    }
+
+    /// Aligns s to the left of a field of `width` characters, filling the end with `fill`, like
+    /// `format!("{:<width$}", s)`. If s fits in the field, the result has a clear length equal to
+    /// `width`, even if the length of s is encrypted.
+    pub fn pad_end(&self, s: &FheString, width: usize, fill: &impl FheCharPattern) -> FheString {
+        self.align(s, width, fill, |_| 0)
+    }
+
+    /// Centers s in a field of `width` characters, filling both sides with `fill`, like
+    /// `format!("{:^width$}", s)`. When the number of fill characters is odd, the extra one is at
+    /// the end. If s fits in the field, the result has a clear length equal to `width`, even if the
+    /// length of s is encrypted.
+    pub fn center(&self, s: &FheString, width: usize, fill: &impl FheCharPattern) -> FheString {
+        self.align(s, width, fill, |fill_length| fill_length / 2)
+    }
+
+    /// Puts s in a field of `width` characters, with `start_fill(fill_length)` fill characters
+    /// before s, where `fill_length` is the total number of fill characters.
+    fn align(
+        &self,
+        s: &FheString,
+        width: usize,
+        fill: &impl FheCharPattern,
+        start_fill: impl Fn(usize) -> usize,
+    ) -> FheString {
+        let s = self.push_padding_to_end(s);
+        let fill = fill.to_fhe_ascii_char(self);
+        let zero = FheAsciiChar(self.create_zero());
+
+        // Character at index i of the result, for s of length `length`.
+        let aligned_char = |i: usize, length: usize| -> &FheAsciiChar {
+            let fill_length = width.saturating_sub(length);
+            let start = start_fill(fill_length);
+            match i {
+                i if i < start => &fill,
+                i if i < start + length => &s.content[i - start],
+                i if i < length + fill_length => &fill,
+                _ => &zero,
+            }
+        };
+
+        match &s.length {
+            FheStrLength::Clear(length) => FheString {
+                content: (0..std::cmp::max(width, *length))
+                    .map(|i| aligned_char(i, *length).clone())
+                    .collect(),
+                padding: Padding::None,
+                length: FheStrLength::Clear(std::cmp::max(width, *length)),
+            },
+            FheStrLength::Encrypted(encrypted_length) => {
+                // The result is selected among the results for each possible length of s.
+                let is_length: Vec<_> = (0..=s.content.len())
+                    .map(|length| {
+                        self.integer_key
+                            .scalar_eq_parallelized(encrypted_length, length as u64)
+                    })
+                    .collect();
+                let content = (0..std::cmp::max(width, s.content.len()))
+                    .map(|i| {
+                        let mut result = zero.0.clone();
+                        for (length, is_length) in is_length.iter().enumerate() {
+                            result = self.integer_key.cmux_parallelized(
+                                is_length,
+                                &aligned_char(i, length).0,
+                                &result,
+                            );
+                        }
+                        FheAsciiChar(result)
+                    })
+                    .collect();
+                if s.content.len() <= width {
+                    FheString {
+                        content,
+                        padding: Padding::None,
+                        length: FheStrLength::Clear(width),
+                    }
+                } else {
+                    FheString {
+                        content,
+                        padding: Padding::Final,
+                        length: FheStrLength::Encrypted(
+                            self.integer_key
+                                .scalar_max_parallelized(encrypted_length, width as u64),
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{gen_keys_test, FheStrLength};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_align(s: &str, padding: usize, width: usize) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let encrypted_fill = CLIENT_KEY.encrypt_ascii_char(b'*');
+        let results = [
+            (
+                SERVER_KEY.pad_start(&encrypted_s, width, &'*'),
+                format!("{:*>width$}", s),
+            ),
+            (
+                SERVER_KEY.pad_end(&encrypted_s, width, &encrypted_fill),
+                format!("{:*<width$}", s),
+            ),
+            (
+                SERVER_KEY.center(&encrypted_s, width, &'*'),
+                format!("{:*^width$}", s),
+            ),
+        ];
+        for (result, expected) in results {
+            assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), expected);
+            if s.len() + padding <= width {
+                assert!(matches!(result.len(), FheStrLength::Clear(l) if *l == width));
+            }
+        }
+    }
+
+    #[test]
+    fn test_align() {
+        check_align("abc", 0, 6);
+        check_align("abc", 2, 6);
+        check_align("abc", 2, 4);
+        check_align("abcd", 1, 2);
+        check_align("", 1, 3);
+    }
+}
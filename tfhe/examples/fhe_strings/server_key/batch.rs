@@ -0,0 +1,123 @@
+use crate::ciphertext::FheString;
+use crate::pattern::FhePattern;
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::StringServerKey;
+use rayon::prelude::*;
+use tfhe::integer::BooleanBlock;
+
+impl StringServerKey {
+    /// Applies `contains` to every string of `strings` in parallel.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let rows: Vec<_> = ["alice", "bob", "carol"]
+    ///     .iter()
+    ///     .map(|s| client_key.encrypt_str(s).unwrap())
+    ///     .collect();
+    /// let result = server_key.contains_batch(&rows, &"o");
+    /// let clear_result: Vec<u32> = result
+    ///     .iter()
+    ///     .map(|b| client_key.decrypt_integer(&server_key.bool_to_radix(b)))
+    ///     .collect();
+    /// assert_eq!(clear_result, vec![0, 1, 1]);
+    /// ```
+    pub fn contains_batch(
+        &self,
+        strings: &[FheString],
+        pattern: &(impl FhePattern + Sync),
+    ) -> Vec<BooleanBlock> {
+        self.map_batch(strings, |s| self.contains(s, pattern))
+    }
+
+    /// Applies `eq` to every string of `strings` in parallel.
+    pub fn eq_batch(
+        &self,
+        strings: &[FheString],
+        pattern: &(impl FhePattern + Sync),
+    ) -> Vec<BooleanBlock> {
+        self.map_batch(strings, |s| self.eq(s, pattern))
+    }
+
+    /// Applies `find` to every string of `strings` in parallel.
+    pub fn find_batch(
+        &self,
+        strings: &[FheString],
+        pattern: &(impl FhePattern + Sync),
+    ) -> Vec<FheOptionInt> {
+        self.map_batch(strings, |s| self.find(s, pattern))
+    }
+
+    /// Applies `to_lowercase` to every string of `strings` in parallel.
+    pub fn to_lowercase_batch(&self, strings: &[FheString]) -> Vec<FheString> {
+        self.map_batch(strings, |s| self.to_lowercase(s))
+    }
+
+    /// Maps `f` over `strings` in parallel, one task per string, which is cheap next to the cost of
+    /// an operation on a string. The order of the results is the order of `strings`.
+    fn map_batch<T: Send>(
+        &self,
+        strings: &[FheString],
+        f: impl Fn(&FheString) -> T + Sync + Send,
+    ) -> Vec<T> {
+        strings.par_iter().with_max_len(1).map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{gen_keys_test, FheString};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    const ROWS: [&str; 4] = ["Alice", "bob", "Bobby", ""];
+
+    fn encrypted_rows() -> Vec<FheString> {
+        ROWS.iter()
+            .enumerate()
+            .map(|(i, s)| CLIENT_KEY.encrypt_str_random_padding(s, i % 2).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_batch() {
+        let rows = encrypted_rows();
+        let pattern = CLIENT_KEY.encrypt_str("b").unwrap();
+
+        let contains = SERVER_KEY.contains_batch(&rows, &pattern);
+        let expected: Vec<bool> = ROWS.iter().map(|s| s.contains('b')).collect();
+        let clear_contains: Vec<bool> = contains
+            .iter()
+            .map(|b| CLIENT_KEY.decrypt_bool(b))
+            .collect();
+        assert_eq!(clear_contains, expected);
+
+        let eq = SERVER_KEY.eq_batch(&rows, &"bob");
+        let expected: Vec<bool> = ROWS.iter().map(|s| *s == "bob").collect();
+        let clear_eq: Vec<bool> = eq.iter().map(|b| CLIENT_KEY.decrypt_bool(b)).collect();
+        assert_eq!(clear_eq, expected);
+
+        let find = SERVER_KEY.find_batch(&rows, &'o');
+        for (s, (found, index)) in ROWS.iter().zip(find.iter()) {
+            let result = CLIENT_KEY
+                .decrypt_bool(found)
+                .then(|| CLIENT_KEY.decrypt_integer(index) as usize);
+            assert_eq!(result, s.find('o'));
+        }
+
+        let lowercase: Vec<String> = SERVER_KEY
+            .to_lowercase_batch(&rows)
+            .iter()
+            .map(|s| CLIENT_KEY.decrypt_string(s).unwrap())
+            .collect();
+        let expected: Vec<String> = ROWS.iter().map(|s| s.to_lowercase()).collect();
+        assert_eq!(lowercase, expected);
+    }
+}
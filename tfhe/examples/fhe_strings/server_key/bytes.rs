@@ -0,0 +1,382 @@
+use crate::ciphertext::{ClearOrEncrypted, FheAsciiChar, FheBytes, FheStrLength};
+use crate::pattern::FheSlicePattern;
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// The result of the split of an `FheBytes`. Only the `number_parts` first elements of `parts` are
+/// parts of the split, the following ones are empty.
+pub struct FheBytesSplit {
+    pub parts: Vec<FheBytes>,
+    pub number_parts: RadixCiphertext,
+}
+
+impl StringServerKey {
+    /// Checks if the byte strings `bytes` and `pattern` are equal. Works for single byte and byte
+    /// string patterns, clear or encrypted.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_bytes = client_key.encrypt_bytes_padding(&[0, 1, 0], 2);
+    /// let result = server_key.eq_bytes(&encrypted_bytes, &[0u8, 1, 0].as_slice());
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn eq_bytes(&self, bytes: &FheBytes, pattern: &impl FheSlicePattern) -> BooleanBlock {
+        let pattern_length = pattern.length_radix(self);
+        let same_length = match &bytes.length {
+            ClearOrEncrypted::Clear(length) => self
+                .integer_key
+                .scalar_eq_parallelized(&pattern_length, *length as u64),
+            ClearOrEncrypted::Encrypted(length) => {
+                self.integer_key.eq_parallelized(&pattern_length, length)
+            }
+        };
+        self.integer_key.boolean_bitand(
+            &same_length,
+            &pattern.is_prefix_of_slice(self, &bytes.content),
+        )
+    }
+
+    /// Checks if `bytes` starts with `pattern`. Works for single byte and byte string patterns,
+    /// clear or encrypted.
+    pub fn starts_with_bytes(
+        &self,
+        bytes: &FheBytes,
+        pattern: &impl FheSlicePattern,
+    ) -> BooleanBlock {
+        self.is_at_index_of_bytes(bytes, pattern, &pattern.pattern_length(), 0)
+    }
+
+    /// Searches for `pattern` in `bytes`. Returns an FheOptionInt Some(n) if pattern is found and
+    /// first starts at index n, None otherwise. When the pattern is not found, the index is the
+    /// length of `bytes`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_bytes = client_key.encrypt_bytes(&[1, 0, 2, 0]);
+    /// let result = server_key.find_bytes(&encrypted_bytes, &0u8);
+    /// let clear_result_bool = client_key.decrypt_integer(&server_key.bool_to_radix(&result.0));
+    /// assert_eq!(clear_result_bool, 1);
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 1);
+    /// ```
+    pub fn find_bytes(&self, bytes: &FheBytes, pattern: &impl FheSlicePattern) -> FheOptionInt {
+        self.find_bytes_from(bytes, pattern, &self.create_zero())
+    }
+
+    /// Searches for the first occurence of `pattern` in `bytes` starting at an index greater than
+    /// or equal to `from`. When the pattern is not found, the index is the length of `bytes`.
+    pub fn find_bytes_from(
+        &self,
+        bytes: &FheBytes,
+        pattern: &impl FheSlicePattern,
+        from: &RadixCiphertext,
+    ) -> FheOptionInt {
+        let pattern_length = pattern.pattern_length();
+        let mut found = self.create_false();
+        let mut index = self.add_length_to_radix(&self.create_zero(), &bytes.length);
+        for n in 0..=bytes.content.len() {
+            let mut match_at_n = self.is_at_index_of_bytes(bytes, pattern, &pattern_length, n);
+            self.integer_key.boolean_bitand_assign(
+                &mut match_at_n,
+                &self.integer_key.scalar_le_parallelized(from, n as u64),
+            );
+            let first_match = self
+                .integer_key
+                .boolean_bitand(&match_at_n, &self.integer_key.boolean_bitnot(&found));
+            index =
+                self.integer_key
+                    .cmux_parallelized(&first_match, &self.create_n(n as u8), &index);
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &match_at_n);
+        }
+        (found, index)
+    }
+
+    /// Returns the bytes of `bytes` from index `start` included to index `end` excluded. Indices
+    /// are clamped to the length of `bytes`, so the result is empty when `start >= end`.
+    pub fn substring_bytes(&self, bytes: &FheBytes, start: usize, end: usize) -> FheBytes {
+        let content_end = end.min(bytes.content.len());
+        let content_start = start.min(content_end);
+        let length = match &bytes.length {
+            ClearOrEncrypted::Clear(length) => {
+                FheStrLength::Clear(end.min(*length).saturating_sub(start))
+            }
+            ClearOrEncrypted::Encrypted(length) => {
+                let end_in_range = self.integer_key.scalar_min_parallelized(length, end as u64);
+                let start_in_range = self
+                    .integer_key
+                    .scalar_min_parallelized(&end_in_range, start as u64);
+                FheStrLength::Encrypted(
+                    self.integer_key
+                        .sub_parallelized(&end_in_range, &start_in_range),
+                )
+            }
+        };
+        FheBytes {
+            content: bytes.content[content_start..content_end].to_vec(),
+            length,
+        }
+    }
+
+    /// Returns the bytes of `bytes` from the encrypted index `start` included to the encrypted
+    /// index `end` excluded. Indices are clamped to the length of `bytes`, so the result is empty
+    /// when `start >= end`. The cost is O(n log(n)) with n the length of the content of `bytes`.
+    pub fn substring_bytes_encrypted(
+        &self,
+        bytes: &FheBytes,
+        start: &RadixCiphertext,
+        end: &RadixCiphertext,
+    ) -> FheBytes {
+        let length = self.add_length_to_radix(&self.create_zero(), &bytes.length);
+        let end_in_range = self.integer_key.min_parallelized(end, &length);
+        let start_in_range = self.integer_key.min_parallelized(start, &end_in_range);
+
+        // The content is shifted by start_in_range one bit at a time, so that the byte at index i
+        // of the result is the byte at index start + i of `bytes`.
+        let zero = FheAsciiChar(self.create_zero());
+        let mut content = bytes.content.clone();
+        let mut shift = 1;
+        while shift <= content.len() {
+            let shift_bit = self.integer_key.scalar_ne_parallelized(
+                &self
+                    .integer_key
+                    .scalar_bitand_parallelized(&start_in_range, shift as u64),
+                0,
+            );
+            content = (0..content.len())
+                .map(|i| {
+                    self.cmux_char(
+                        &shift_bit,
+                        content.get(i + shift).unwrap_or(&zero),
+                        &content[i],
+                    )
+                })
+                .collect();
+            shift *= 2;
+        }
+        FheBytes {
+            content,
+            length: FheStrLength::Encrypted(
+                self.integer_key
+                    .sub_parallelized(&end_in_range, &start_in_range),
+            ),
+        }
+    }
+
+    /// Splits `bytes` at each occurence of `pattern`, like `split` from the standard library on
+    /// slices of bytes. An empty pattern splits between each byte and produces an empty first and
+    /// last part. The pattern is compared once at each position of `bytes` and each of the
+    /// n + 2 parts is cut with `substring_bytes_encrypted`, so the cost is
+    /// O(n * (m + n log(n))) with n the length of the content of `bytes` and m the length of the
+    /// pattern.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_bytes = client_key.encrypt_bytes(&[1, 0, 2]);
+    /// let result = server_key.split_bytes(&encrypted_bytes, &0u8);
+    /// let number_parts = client_key.decrypt_integer(&result.number_parts);
+    /// assert_eq!(number_parts, 2);
+    /// assert_eq!(client_key.decrypt_bytes(&result.parts[0]), vec![1]);
+    /// assert_eq!(client_key.decrypt_bytes(&result.parts[1]), vec![2]);
+    /// ```
+    pub fn split_bytes(&self, bytes: &FheBytes, pattern: &impl FheSlicePattern) -> FheBytesSplit {
+        let max_number_cuts = bytes.content.len() + 1;
+        let zero = self.create_zero();
+        let length = self.add_length_to_radix(&zero, &bytes.length);
+        let pattern_length = pattern.pattern_length();
+        let pattern_length_radix = pattern.length_radix(self);
+        // Occurences of the empty pattern are one byte apart.
+        let step = self
+            .integer_key
+            .scalar_max_parallelized(&pattern_length_radix, 1);
+        let mut is_cut = vec![self.create_false(); max_number_cuts];
+        let mut cut_index = vec![length.clone(); max_number_cuts];
+        let mut count = zero.clone();
+        // The first position where the next occurence can start.
+        let mut limit = zero.clone();
+
+        for position in 0..=bytes.content.len() {
+            let current_match = self.integer_key.boolean_bitand(
+                &self.is_at_index_of_bytes(bytes, pattern, &pattern_length, position),
+                &self
+                    .integer_key
+                    .scalar_le_parallelized(&limit, position as u64),
+            );
+            for (k, (is_cut, cut_index)) in is_cut.iter_mut().zip(&mut cut_index).enumerate() {
+                let is_kth = self.integer_key.boolean_bitand(
+                    &current_match,
+                    &self.integer_key.scalar_eq_parallelized(&count, k as u64),
+                );
+                *cut_index = self.integer_key.cmux_parallelized(
+                    &is_kth,
+                    &self.create_n(position as u8),
+                    cut_index,
+                );
+                self.integer_key.boolean_bitor_assign(is_cut, &is_kth);
+            }
+            self.integer_key
+                .add_assign_parallelized(&mut count, &self.bool_to_radix(&current_match));
+            limit = self.integer_key.cmux_parallelized(
+                &current_match,
+                &self
+                    .integer_key
+                    .scalar_add_parallelized(&step, position as u64),
+                &limit,
+            );
+        }
+
+        let mut parts = Vec::with_capacity(max_number_cuts + 1);
+        let mut number_parts = self.create_n(1);
+        let mut start_part = zero;
+        for (is_cut, end_part) in is_cut.iter().zip(cut_index) {
+            parts.push(self.substring_bytes_encrypted(bytes, &start_part, &end_part));
+            self.integer_key
+                .add_assign_parallelized(&mut number_parts, &self.bool_to_radix(is_cut));
+            start_part = self
+                .integer_key
+                .add_parallelized(&end_part, &pattern_length_radix);
+        }
+        parts.push(self.substring_bytes_encrypted(bytes, &start_part, &length));
+        FheBytesSplit {
+            parts,
+            number_parts,
+        }
+    }
+
+    /// Checks if `pattern`, of length `pattern_length`, occurs in `bytes` at index `n`. The content
+    /// of `bytes` is not padded by zeros, so the end of the match is checked against the length of
+    /// `bytes` instead.
+    fn is_at_index_of_bytes(
+        &self,
+        bytes: &FheBytes,
+        pattern: &impl FheSlicePattern,
+        pattern_length: &FheStrLength,
+        n: usize,
+    ) -> BooleanBlock {
+        self.integer_key.boolean_bitand(
+            &pattern.is_prefix_of_slice(self, &bytes.content[n..]),
+            &self.bytes_fit_at_index(bytes, pattern_length, n),
+        )
+    }
+
+    /// Checks that a pattern of length `pattern_length` starting at index `n` ends before the end
+    /// of `bytes`.
+    pub fn bytes_fit_at_index(
+        &self,
+        bytes: &FheBytes,
+        pattern_length: &FheStrLength,
+        n: usize,
+    ) -> BooleanBlock {
+        match (pattern_length, &bytes.length) {
+            (ClearOrEncrypted::Clear(pattern_length), ClearOrEncrypted::Clear(length)) => {
+                match n + pattern_length <= *length {
+                    true => self.create_true(),
+                    false => self.create_false(),
+                }
+            }
+            (ClearOrEncrypted::Clear(pattern_length), ClearOrEncrypted::Encrypted(length)) => self
+                .integer_key
+                .scalar_ge_parallelized(length, (n + pattern_length) as u64),
+            (ClearOrEncrypted::Encrypted(_), ClearOrEncrypted::Clear(length)) if n > *length => {
+                self.create_false()
+            }
+            (ClearOrEncrypted::Encrypted(pattern_length), ClearOrEncrypted::Clear(length)) => self
+                .integer_key
+                .scalar_le_parallelized(pattern_length, (length - n) as u64),
+            (ClearOrEncrypted::Encrypted(pattern_length), ClearOrEncrypted::Encrypted(length)) => {
+                self.integer_key.le_parallelized(
+                    &self
+                        .integer_key
+                        .scalar_add_parallelized(pattern_length, n as u64),
+                    length,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_eq_starts_with_bytes() {
+        let bytes = CLIENT_KEY.encrypt_bytes_padding(&[0, 1, 0], 2);
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_bytes(&bytes, &[0u8, 1, 0].as_slice())));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_bytes(&bytes, &[0u8, 1, 0, 0].as_slice())));
+        let encrypted_pattern = CLIENT_KEY.encrypt_bytes_padding(&[0, 1, 0], 1);
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_bytes(&bytes, &encrypted_pattern)));
+        let encrypted_prefix = CLIENT_KEY.encrypt_bytes_padding(&[0, 1], 1);
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_bytes(&bytes, &encrypted_prefix)));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_bytes(&bytes, &encrypted_prefix)));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_bytes(&bytes, &0u8)));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_bytes(&bytes, &1u8)));
+    }
+
+    #[test]
+    fn test_find_bytes() {
+        let bytes = CLIENT_KEY.encrypt_bytes_padding(&[2, 0, 1, 0], 1);
+        let (found, index) = SERVER_KEY.find_bytes(&bytes, &[0u8, 1].as_slice());
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 1);
+
+        let pattern = CLIENT_KEY.encrypt_bytes_padding(&[1, 0], 2);
+        let (found, index) = SERVER_KEY.find_bytes(&bytes, &pattern);
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 2);
+
+        // The padding byte after the end must not be matched.
+        let (found, _) = SERVER_KEY.find_bytes(&bytes, &[0u8, 0].as_slice());
+        assert!(!CLIENT_KEY.decrypt_bool(&found));
+    }
+
+    #[test]
+    fn test_substring_bytes() {
+        let bytes = CLIENT_KEY.encrypt_bytes_padding(&[0, 1, 2, 0], 1);
+        let result = SERVER_KEY.substring_bytes(&bytes, 1, 4);
+        assert_eq!(CLIENT_KEY.decrypt_bytes(&result), vec![1, 2, 0]);
+        let result = SERVER_KEY.substring_bytes(&bytes, 3, 10);
+        assert_eq!(CLIENT_KEY.decrypt_bytes(&result), vec![0]);
+
+        let start = CLIENT_KEY.encrypt_integer(2u8);
+        let end = CLIENT_KEY.encrypt_integer(6u8);
+        let result = SERVER_KEY.substring_bytes_encrypted(&bytes, &start, &end);
+        assert_eq!(CLIENT_KEY.decrypt_bytes(&result), vec![2, 0]);
+    }
+
+    #[test]
+    fn test_split_bytes() {
+        let bytes = CLIENT_KEY.encrypt_bytes_padding(&[1, 0, 0, 2], 1);
+        let result = SERVER_KEY.split_bytes(&bytes, &0u8);
+        assert_eq!(CLIENT_KEY.decrypt_integer(&result.number_parts), 3);
+        let parts: Vec<Vec<u8>> = result.parts[..3]
+            .iter()
+            .map(|part| CLIENT_KEY.decrypt_bytes(part))
+            .collect();
+        assert_eq!(parts, vec![vec![1], vec![], vec![2]]);
+
+        let bytes = CLIENT_KEY.encrypt_bytes(&[1, 2]);
+        let result = SERVER_KEY.split_bytes(&bytes, &[].as_slice());
+        assert_eq!(CLIENT_KEY.decrypt_integer(&result.number_parts), 4);
+        let parts: Vec<Vec<u8>> = result
+            .parts
+            .iter()
+            .map(|part| CLIENT_KEY.decrypt_bytes(part))
+            .collect();
+        assert_eq!(parts, vec![vec![], vec![1], vec![2], vec![]]);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ciphertext::{FheAsciiChar, FheString};
 use crate::server_key::StringServerKey;
+use tfhe::integer::BooleanBlock;
 
 pub const UP_LOW_DISTANCE: u8 = 32;
 
@@ -49,8 +50,95 @@ impl StringServerKey {
         }
     }
 
+    /// Returns a encrypted string encoding s with its first character in uppercase and the other
+    /// ones in lowercase. The first character is the first non padding one, whatever the padding
+    /// of s.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str_random_padding("hELLO", 2).unwrap();
+    /// let encrypted_str_capital = server_key.capitalize(&encrypted_str);
+    /// let decrypted_str_capital = client_key.decrypt_string(&encrypted_str_capital).unwrap();
+    /// assert_eq!(&decrypted_str_capital, "Hello");
+    /// ```
+    pub fn capitalize(&self, s: &FheString) -> FheString {
+        self.change_case_at_word_starts(s, |_| self.create_false())
+    }
+
+    /// Returns a encrypted string encoding s with the first character of each word in uppercase
+    /// and the other ones in lowercase, words being separated by ASCII whitespaces.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("hello  wORLD").unwrap();
+    /// let encrypted_str_title = server_key.to_title_case(&encrypted_str);
+    /// let decrypted_str_title = client_key.decrypt_string(&encrypted_str_title).unwrap();
+    /// assert_eq!(&decrypted_str_title, "Hello  World");
+    /// ```
+    pub fn to_title_case(&self, s: &FheString) -> FheString {
+        self.change_case_at_word_starts(s, |c| self.is_ascii_white_space(c))
+    }
+
+    /// Returns a encrypted string encoding s with lowercase characters in uppercase and uppercase
+    /// characters in lowercase.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("aB.").unwrap();
+    /// let encrypted_str_swapped = server_key.swap_case(&encrypted_str);
+    /// let decrypted_str_swapped = client_key.decrypt_string(&encrypted_str_swapped).unwrap();
+    /// assert_eq!(&decrypted_str_swapped, "Ab.");
+    /// ```
+    pub fn swap_case(&self, s: &FheString) -> FheString {
+        FheString {
+            content: s.content.iter().map(|c| self.swap_case_char(c)).collect(),
+            padding: s.padding,
+            length: s.length.clone(),
+        }
+    }
+
+    /// Puts in uppercase the characters starting a word and in lowercase the other ones. A
+    /// character starts a word if it is the first non padding character, or if the previous non
+    /// padding character is a separator. Padding characters are skipped, so that any padding is
+    /// handled without being pushed to the end.
+    fn change_case_at_word_starts(
+        &self,
+        s: &FheString,
+        is_separator: impl Fn(&FheAsciiChar) -> BooleanBlock,
+    ) -> FheString {
+        let mut starts_word = self.create_true();
+        let mut content = Vec::with_capacity(s.content.len());
+        for c in &s.content {
+            // Padding zeros are unchanged by both case changes.
+            content.push(self.cmux_char(
+                &starts_word,
+                &self.to_uppercase_char(c),
+                &self.to_lowercase_char(c),
+            ));
+            let is_padding = self.eq_clear_char(c, 0);
+            starts_word = self.integer_key.boolean_bitor(
+                &self.integer_key.boolean_bitand(&is_padding, &starts_word),
+                &self.integer_key.boolean_bitand(
+                    &self.integer_key.boolean_bitnot(&is_padding),
+                    &is_separator(c),
+                ),
+            );
+        }
+        FheString {
+            content,
+            padding: s.padding,
+            length: s.length.clone(),
+        }
+    }
+
     /// Returns a encrypted character encoding the same as c in uppercase.
     pub fn to_uppercase_char(&self, c: &FheAsciiChar) -> FheAsciiChar {
+        if let Some(clear_c) = c.clear_value() {
+            return self.server_encrypt_ascii_char(clear_c.to_ascii_uppercase() as char);
+        }
         let change_case = &self.integer_key.boolean_bitand(
             &self.integer_key.scalar_gt_parallelized(&c.0, 96),
             &self.integer_key.scalar_lt_parallelized(&c.0, 123),
@@ -67,6 +155,9 @@ impl StringServerKey {
 
     /// Returns a encrypted character encoding the same as c in lowercase.
     pub fn to_lowercase_char(&self, c: &FheAsciiChar) -> FheAsciiChar {
+        if let Some(clear_c) = c.clear_value() {
+            return self.server_encrypt_ascii_char(clear_c.to_ascii_lowercase() as char);
+        }
         let change_case = &self.integer_key.boolean_bitand(
             &self.integer_key.scalar_gt_parallelized(&c.0, 64),
             &self.integer_key.scalar_lt_parallelized(&c.0, 91),
@@ -80,6 +171,40 @@ impl StringServerKey {
             ),
         )
     }
+
+    /// Returns a encrypted character encoding c in uppercase if it is in lowercase, and in
+    /// lowercase if it is in uppercase.
+    pub fn swap_case_char(&self, c: &FheAsciiChar) -> FheAsciiChar {
+        if let Some(clear_c) = c.clear_value() {
+            let swapped = match clear_c.is_ascii_lowercase() {
+                true => clear_c.to_ascii_uppercase(),
+                false => clear_c.to_ascii_lowercase(),
+            };
+            return self.server_encrypt_ascii_char(swapped as char);
+        }
+        let is_lowercase = self.integer_key.boolean_bitand(
+            &self.integer_key.scalar_gt_parallelized(&c.0, 96),
+            &self.integer_key.scalar_lt_parallelized(&c.0, 123),
+        );
+        let is_uppercase = self.integer_key.boolean_bitand(
+            &self.integer_key.scalar_gt_parallelized(&c.0, 64),
+            &self.integer_key.scalar_lt_parallelized(&c.0, 91),
+        );
+        FheAsciiChar(
+            self.integer_key.sub_parallelized(
+                &self.integer_key.add_parallelized(
+                    &c.0,
+                    &self.integer_key.scalar_mul_parallelized(
+                        &self.bool_to_radix(&is_uppercase),
+                        UP_LOW_DISTANCE,
+                    ),
+                ),
+                &self
+                    .integer_key
+                    .scalar_mul_parallelized(&self.bool_to_radix(&is_lowercase), UP_LOW_DISTANCE),
+            ),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -110,4 +235,42 @@ mod tests {
         let decrypted_str_lower = CLIENT_KEY.decrypt_string(&encrypted_str_lower).unwrap();
         assert_eq!(&decrypted_str_lower, "bcd");
     }
+
+    #[test]
+    fn test_capitalize_title_swap_case() {
+        for (s, padding) in [("hELLO wORLD", 2), (" two\twords ", 3), ("", 1), ("Ab", 0)] {
+            let encrypted_str = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+            let capitalize = SERVER_KEY.capitalize(&encrypted_str);
+            let mut chars = s.chars();
+            let expected: String = match chars.next() {
+                Some(first) => {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_lowercase()
+                }
+                None => String::new(),
+            };
+            assert_eq!(CLIENT_KEY.decrypt_string(&capitalize).unwrap(), expected);
+
+            let title = SERVER_KEY.to_title_case(&encrypted_str);
+            let mut expected = String::new();
+            let mut starts_word = true;
+            for c in s.chars() {
+                match starts_word {
+                    true => expected.push(c.to_ascii_uppercase()),
+                    false => expected.push(c.to_ascii_lowercase()),
+                }
+                starts_word = c.is_ascii_whitespace();
+            }
+            assert_eq!(CLIENT_KEY.decrypt_string(&title).unwrap(), expected);
+
+            let swapped = SERVER_KEY.swap_case(&encrypted_str);
+            let expected: String = s
+                .chars()
+                .map(|c| match c.is_ascii_lowercase() {
+                    true => c.to_ascii_uppercase(),
+                    false => c.to_ascii_lowercase(),
+                })
+                .collect();
+            assert_eq!(CLIENT_KEY.decrypt_string(&swapped).unwrap(), expected);
+        }
+    }
 }
@@ -76,7 +76,7 @@ impl StringServerKey {
         let mut result = self.create_zero();
 
         for c in content_slice {
-            let current_is_zero = self.integer_key.scalar_eq_parallelized(&c.0, 0);
+            let current_is_zero = self.eq_clear_char(c, 0);
 
             let first_non_null = self.integer_key.boolean_bitand(
                 &previous_is_padding_zero,
@@ -111,7 +111,7 @@ impl StringServerKey {
         let mut result = self.create_zero();
 
         for c in content_slice.iter_mut().rev() {
-            let current_is_zero = self.integer_key.scalar_eq_parallelized(&c.0, 0);
+            let current_is_zero = self.eq_clear_char(c, 0);
 
             let first_non_null = self.integer_key.boolean_bitand(
                 &previous_is_padding_zero,
@@ -0,0 +1,212 @@
+use crate::ciphertext::{FheString, Padding};
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::StringServerKey;
+use std::collections::HashMap;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// Cache of the comparisons between the characters of an encrypted needle and the characters of a
+/// clear haystack. The entry `(i, c)` is an encryption of true if the character of index i of the
+/// needle is either `c` or a padding zero. A haystack has few distinct characters, so the number
+/// of homomorphic comparisons is much smaller than the number of pairs of indices.
+struct NeedleCache<'a> {
+    needle: &'a FheString,
+    comparisons: HashMap<(usize, u8), BooleanBlock>,
+}
+
+impl<'a> NeedleCache<'a> {
+    fn new(needle: &'a FheString) -> Self {
+        NeedleCache {
+            needle,
+            comparisons: HashMap::new(),
+        }
+    }
+
+    fn matches_or_ended(&mut self, server_key: &StringServerKey, i: usize, c: u8) -> BooleanBlock {
+        if let Some(result) = self.comparisons.get(&(i, c)) {
+            return result.clone();
+        }
+        let result = match c {
+            0 => server_key.eq_clear_char(&self.needle.content[i], 0),
+            _ => {
+                let ended = self.matches_or_ended(server_key, i, 0);
+                server_key.integer_key.boolean_bitor(
+                    &server_key.eq_clear_char(&self.needle.content[i], c),
+                    &ended,
+                )
+            }
+        };
+        self.comparisons.insert((i, c), result.clone());
+        result
+    }
+}
+
+impl StringServerKey {
+    /// Searches for the encrypted needle in the clear haystack. Returns an FheOptionInt Some(n) if
+    /// needle is found and first starts at index n, None otherwise. Only scalar comparisons
+    /// against the characters of the haystack are performed.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let needle = client_key.encrypt_str("ba").unwrap();
+    /// let result = server_key.find_in_clear("ababa", &needle);
+    /// let clear_result_bool = client_key.decrypt_integer(&server_key.bool_to_radix(&result.0));
+    /// assert_eq!(clear_result_bool, 1);
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 1);
+    /// ```
+    pub fn find_in_clear(&self, haystack: &str, needle: &FheString) -> FheOptionInt {
+        let needle = self.needle_with_final_padding(needle);
+        let mut cache = NeedleCache::new(&needle);
+        let mut found = self.create_false();
+        let mut index = self.create_zero();
+        // The positions are visited backwards, so the smallest matching one is written last.
+        for n in (0..=haystack.len()).rev() {
+            let current_match = self.needle_matches_clear_at(&mut cache, haystack.as_bytes(), n);
+            index =
+                self.integer_key
+                    .cmux_parallelized(&current_match, &self.create_n(n as u8), &index);
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &current_match);
+        }
+        (found, index)
+    }
+
+    /// Returns an encryption of true if the clear haystack contains the encrypted needle.
+    pub fn contains_in_clear(&self, haystack: &str, needle: &FheString) -> BooleanBlock {
+        let needle = self.needle_with_final_padding(needle);
+        let mut cache = NeedleCache::new(&needle);
+        let mut result = self.create_false();
+        for n in 0..=haystack.len() {
+            let current_match = self.needle_matches_clear_at(&mut cache, haystack.as_bytes(), n);
+            self.integer_key
+                .boolean_bitor_assign(&mut result, &current_match);
+        }
+        result
+    }
+
+    /// Returns an encryption of true if the clear haystack starts with the encrypted needle.
+    pub fn starts_with_in_clear(&self, haystack: &str, needle: &FheString) -> BooleanBlock {
+        let needle = self.needle_with_final_padding(needle);
+        let mut cache = NeedleCache::new(&needle);
+        self.needle_matches_clear_at(&mut cache, haystack.as_bytes(), 0)
+    }
+
+    /// Counts the non overlapping occurences of the encrypted needle in the clear haystack, like
+    /// `haystack.matches(needle).count()`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let needle = client_key.encrypt_str("aba").unwrap();
+    /// let result = server_key.count_in_clear("ababa", &needle);
+    /// assert_eq!(client_key.decrypt_integer(&result), 1);
+    /// ```
+    pub fn count_in_clear(&self, haystack: &str, needle: &FheString) -> RadixCiphertext {
+        let needle = self.needle_with_final_padding(needle);
+        let mut cache = NeedleCache::new(&needle);
+        // An empty needle matches at every index, so the next match is at least one index after.
+        let step = self.integer_key.scalar_max_parallelized(
+            &self.add_length_to_radix(&self.create_zero(), &needle.length),
+            1,
+        );
+        let mut count = self.create_zero();
+        let mut next_allowed_index = self.create_zero();
+        for n in 0..=haystack.len() {
+            let mut current_match =
+                self.needle_matches_clear_at(&mut cache, haystack.as_bytes(), n);
+            self.integer_key.boolean_bitand_assign(
+                &mut current_match,
+                &self
+                    .integer_key
+                    .scalar_le_parallelized(&next_allowed_index, n as u64),
+            );
+            self.integer_key
+                .add_assign_parallelized(&mut count, &self.bool_to_radix(&current_match));
+            next_allowed_index = self.integer_key.cmux_parallelized(
+                &current_match,
+                &self.integer_key.scalar_add_parallelized(&step, n as u64),
+                &next_allowed_index,
+            );
+        }
+        count
+    }
+
+    /// Returns an encryption of true if the needle, assumed to have at worst final padding, occurs
+    /// in the haystack at index n.
+    fn needle_matches_clear_at(
+        &self,
+        cache: &mut NeedleCache,
+        haystack: &[u8],
+        n: usize,
+    ) -> BooleanBlock {
+        let mut result = self.create_true();
+        for i in 0..cache.needle.content.len() {
+            // After the end of the haystack, the needle should be padding.
+            let c = haystack.get(n + i).copied().unwrap_or(0);
+            self.integer_key
+                .boolean_bitand_assign(&mut result, &cache.matches_or_ended(self, i, c));
+        }
+        result
+    }
+
+    fn needle_with_final_padding(&self, needle: &FheString) -> FheString {
+        match needle.padding {
+            Padding::None | Padding::Final => needle.clone(),
+            _ => self.push_padding_to_end(needle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_in_clear(haystack: &str, needle: &str, padding: usize) {
+        let encrypted_needle = CLIENT_KEY
+            .encrypt_str_random_padding(needle, padding)
+            .unwrap();
+        let (found, index) = SERVER_KEY.find_in_clear(haystack, &encrypted_needle);
+        let result = CLIENT_KEY
+            .decrypt_bool(&found)
+            .then(|| CLIENT_KEY.decrypt_integer(&index) as usize);
+        assert_eq!(result, haystack.find(needle));
+
+        let contains = SERVER_KEY.contains_in_clear(haystack, &encrypted_needle);
+        assert_eq!(
+            CLIENT_KEY.decrypt_bool(&contains),
+            haystack.contains(needle)
+        );
+
+        let starts_with = SERVER_KEY.starts_with_in_clear(haystack, &encrypted_needle);
+        assert_eq!(
+            CLIENT_KEY.decrypt_bool(&starts_with),
+            haystack.starts_with(needle)
+        );
+
+        let count = SERVER_KEY.count_in_clear(haystack, &encrypted_needle);
+        assert_eq!(
+            CLIENT_KEY.decrypt_integer(&count) as usize,
+            haystack.matches(needle).count()
+        );
+    }
+
+    #[test]
+    fn test_in_clear() {
+        check_in_clear("ababa", "aba", 0);
+        check_in_clear("ababa", "ba", 2);
+        check_in_clear("ababa", "abc", 1);
+        check_in_clear("ab", "abc", 0);
+        check_in_clear("abc", "", 2);
+        check_in_clear("", "", 1);
+    }
+}
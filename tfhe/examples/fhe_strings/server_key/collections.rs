@@ -0,0 +1,413 @@
+use crate::ciphertext::{FheStrLength, FheString, Padding};
+use crate::pattern::FhePattern;
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::split::FheSplit;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+impl StringServerKey {
+    /// Sorts a vector of encrypted strings for the lexicographic order for bytes. The sort is an
+    /// oblivious sorting network (Batcher's merge exchange), the sequence of comparisons does not
+    /// depend on the encrypted content. All strings of the result have final padding, the same
+    /// content length and an encrypted length.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let strings = vec![
+    ///     client_key.encrypt_str("bc").unwrap(),
+    ///     client_key.encrypt_str("a").unwrap(),
+    /// ];
+    /// let sorted = server_key.sort_strings(&strings);
+    /// assert_eq!(client_key.decrypt_string(&sorted[0]).unwrap(), "a");
+    /// assert_eq!(client_key.decrypt_string(&sorted[1]).unwrap(), "bc");
+    /// ```
+    pub fn sort_strings(&self, strings: &[FheString]) -> Vec<FheString> {
+        let in_range = vec![self.create_true(); strings.len()];
+        self.sort_strings_in_range(strings, in_range).0
+    }
+
+    /// Sorts the first `number_parts` parts of `fhe_split`. The parts after `number_parts` are
+    /// pushed after the sorted parts, so that the result is still a valid `FheSplit` with the same
+    /// encrypted number of parts.
+    pub fn sort_split(&self, fhe_split: &FheSplit) -> FheSplit {
+        let in_range = self.split_parts_in_range(fhe_split);
+        FheSplit {
+            parts: self.sort_strings_in_range(&fhe_split.parts, in_range).0,
+            number_parts: fhe_split.number_parts.clone(),
+            current_index: 0,
+        }
+    }
+
+    /// Removes consecutive repeated strings, as `Vec::dedup` from the standard library. Call
+    /// `sort_strings` first to remove all duplicates. The number of remaining strings is encrypted,
+    /// the result is thus returned as an `FheSplit` whose parts vector has the same size as
+    /// `strings`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let strings = vec![
+    ///     client_key.encrypt_str("a").unwrap(),
+    ///     client_key.encrypt_str("a").unwrap(),
+    ///     client_key.encrypt_str("b").unwrap(),
+    /// ];
+    /// let deduped = server_key.dedup(&strings);
+    /// assert_eq!(client_key.decrypt_integer(&deduped.number_parts), 2);
+    /// ```
+    pub fn dedup(&self, strings: &[FheString]) -> FheSplit {
+        let in_range = vec![self.create_true(); strings.len()];
+        self.dedup_in_range(strings, &in_range)
+    }
+
+    /// Same as `dedup` but only considers the first `number_parts` parts of `fhe_split`.
+    pub fn dedup_split(&self, fhe_split: &FheSplit) -> FheSplit {
+        let in_range = self.split_parts_in_range(fhe_split);
+        self.dedup_in_range(&fhe_split.parts, &in_range)
+    }
+
+    /// Returns an encryption of true if one of the strings of `list` is equal to `needle` (a clear
+    /// or encrypted string).
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let list = vec![
+    ///     client_key.encrypt_str("ab").unwrap(),
+    ///     client_key.encrypt_str("cd").unwrap(),
+    /// ];
+    /// let result = server_key.contains_string_in(&list, &"cd");
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn contains_string_in(&self, list: &[FheString], needle: &impl FhePattern) -> BooleanBlock {
+        let mut result = self.create_false();
+        for s in list {
+            self.integer_key
+                .boolean_bitor_assign(&mut result, &self.eq(s, needle));
+        }
+        result
+    }
+
+    /// Same as `contains_string_in` but only considers the first `number_parts` parts of
+    /// `fhe_split`.
+    pub fn contains_string_in_split(
+        &self,
+        fhe_split: &FheSplit,
+        needle: &impl FhePattern,
+    ) -> BooleanBlock {
+        let mut result = self.create_false();
+        for (s, in_range) in fhe_split
+            .parts
+            .iter()
+            .zip(self.split_parts_in_range(fhe_split))
+        {
+            let current_match = self
+                .integer_key
+                .boolean_bitand(&self.eq(s, needle), &in_range);
+            self.integer_key
+                .boolean_bitor_assign(&mut result, &current_match);
+        }
+        result
+    }
+
+    /// Returns an FheOptionInt Some(n) if `needle` (a clear or encrypted string) is equal to the
+    /// `n`-th string of `list` and to none of the previous ones, None otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let list = vec![
+    ///     client_key.encrypt_str("ab").unwrap(),
+    ///     client_key.encrypt_str("cd").unwrap(),
+    /// ];
+    /// let result = server_key.index_of(&list, &"cd");
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 1);
+    /// ```
+    pub fn index_of(&self, list: &[FheString], needle: &impl FhePattern) -> FheOptionInt {
+        let in_range = vec![self.create_true(); list.len()];
+        self.index_of_in_range(list, &in_range, needle)
+    }
+
+    /// Same as `index_of` but only considers the first `number_parts` parts of `fhe_split`.
+    pub fn index_of_in_split(
+        &self,
+        fhe_split: &FheSplit,
+        needle: &impl FhePattern,
+    ) -> FheOptionInt {
+        let in_range = self.split_parts_in_range(fhe_split);
+        self.index_of_in_range(&fhe_split.parts, &in_range, needle)
+    }
+
+    /// Returns, for each part of `fhe_split`, an encryption of true if the index of the part is
+    /// lower than `number_parts`.
+    pub fn split_parts_in_range(&self, fhe_split: &FheSplit) -> Vec<BooleanBlock> {
+        (0..fhe_split.parts.len())
+            .map(|i| {
+                self.integer_key
+                    .scalar_gt_parallelized(&fhe_split.number_parts, i as u64)
+            })
+            .collect()
+    }
+
+    /// Sorts `strings` with the elements whose `in_range` flag is false considered greater than
+    /// all other elements. Returns the sorted strings together with their `in_range` flags.
+    fn sort_strings_in_range(
+        &self,
+        strings: &[FheString],
+        mut in_range: Vec<BooleanBlock>,
+    ) -> (Vec<FheString>, Vec<BooleanBlock>) {
+        let content_length = strings.iter().map(|s| s.content.len()).max().unwrap_or(0);
+        let mut sorted: Vec<FheString> = strings
+            .iter()
+            .map(|s| self.pad_to_content_length(s, content_length))
+            .collect();
+
+        // Batcher's merge exchange (Knuth, TAOCP vol. 3, Algorithm M), valid for any number of
+        // elements.
+        let n = sorted.len();
+        if n < 2 {
+            return (sorted, in_range);
+        }
+        let t = usize::BITS - (n - 1).leading_zeros();
+        let mut p = 1 << (t - 1);
+        while p > 0 {
+            let mut q = 1 << (t - 1);
+            let mut r = 0;
+            let mut d = p;
+            while d > 0 {
+                for i in 0..(n - d) {
+                    if i & p == r {
+                        self.compare_and_swap(&mut sorted, &mut in_range, i, i + d);
+                    }
+                }
+                d = q - p;
+                q >>= 1;
+                r = p;
+            }
+            p >>= 1;
+        }
+        (sorted, in_range)
+    }
+
+    /// Swaps the `i`-th and `j`-th strings (for `i` < `j`) if they are not in order. Elements out
+    /// of range are greater than any element in range. Assumes that all strings have final
+    /// padding and the same content length.
+    fn compare_and_swap(
+        &self,
+        strings: &mut [FheString],
+        in_range: &mut [BooleanBlock],
+        i: usize,
+        j: usize,
+    ) {
+        let not_ordered = self
+            .integer_key
+            .boolean_bitnot(&self.compare_no_init_padding(
+                &strings[i],
+                &strings[j],
+                std::cmp::Ordering::Less,
+            ));
+        let swap = self.integer_key.boolean_bitand(
+            &in_range[j],
+            &self
+                .integer_key
+                .boolean_bitor(&self.integer_key.boolean_bitnot(&in_range[i]), &not_ordered),
+        );
+
+        let new_i = self.cmux_same_content_length(&swap, &strings[j], &strings[i]);
+        let new_j = self.cmux_same_content_length(&swap, &strings[i], &strings[j]);
+        strings[i] = new_i;
+        strings[j] = new_j;
+
+        let new_in_range_i = self.radix_to_bool(&self.integer_key.cmux_parallelized(
+            &swap,
+            &self.bool_to_radix(&in_range[j]),
+            &self.bool_to_radix(&in_range[i]),
+        ));
+        let new_in_range_j = self.radix_to_bool(&self.integer_key.cmux_parallelized(
+            &swap,
+            &self.bool_to_radix(&in_range[i]),
+            &self.bool_to_radix(&in_range[j]),
+        ));
+        in_range[i] = new_in_range_i;
+        in_range[j] = new_in_range_j;
+    }
+
+    /// Implementation of `dedup` where only the strings whose `in_range` flag is true are
+    /// considered. The kept strings are compacted at the begining of the parts of the result.
+    fn dedup_in_range(&self, strings: &[FheString], in_range: &[BooleanBlock]) -> FheSplit {
+        let content_length = strings.iter().map(|s| s.content.len()).max().unwrap_or(0);
+        let padded: Vec<FheString> = strings
+            .iter()
+            .map(|s| self.pad_to_content_length(s, content_length))
+            .collect();
+
+        // A string is kept if it is in range and different from the previous one.
+        let mut keep: Vec<BooleanBlock> = Vec::with_capacity(padded.len());
+        for (i, s) in padded.iter().enumerate() {
+            if i == 0 {
+                keep.push(in_range[0].clone());
+            } else {
+                let is_duplicate = self.eq_no_init_padding(s, &padded[i - 1]);
+                keep.push(self.integer_key.boolean_bitand(
+                    &in_range[i],
+                    &self.integer_key.boolean_bitnot(&is_duplicate),
+                ));
+            }
+        }
+
+        // `rank[i]` holds the index of the `i`-th string in the result, if it is kept.
+        let mut number_parts: RadixCiphertext = self.create_zero();
+        let mut rank: Vec<RadixCiphertext> = Vec::with_capacity(padded.len());
+        for k in &keep {
+            rank.push(number_parts.clone());
+            self.integer_key
+                .add_assign_parallelized(&mut number_parts, &self.bool_to_radix(k));
+        }
+
+        // Compaction: the `j`-th part of the result is the kept string of rank `j`.
+        let empty_string = self.pad_to_content_length(
+            &FheString {
+                content: vec![],
+                padding: Padding::None,
+                length: FheStrLength::Clear(0),
+            },
+            content_length,
+        );
+        let mut parts: Vec<FheString> = Vec::with_capacity(padded.len());
+        for j in 0..padded.len() {
+            let mut part = empty_string.clone();
+            for i in j..padded.len() {
+                let selected = self.integer_key.boolean_bitand(
+                    &keep[i],
+                    &self.integer_key.scalar_eq_parallelized(&rank[i], j as u64),
+                );
+                part = self.cmux_same_content_length(&selected, &padded[i], &part);
+            }
+            parts.push(part);
+        }
+        FheSplit {
+            parts,
+            number_parts,
+            current_index: 0,
+        }
+    }
+
+    /// Implementation of `index_of` where only the strings whose `in_range` flag is true are
+    /// considered.
+    fn index_of_in_range(
+        &self,
+        list: &[FheString],
+        in_range: &[BooleanBlock],
+        needle: &impl FhePattern,
+    ) -> FheOptionInt {
+        let mut found = self.create_false();
+        let mut index = self.create_zero();
+        for (s, s_in_range) in list.iter().zip(in_range) {
+            let current_match = self
+                .integer_key
+                .boolean_bitand(&self.eq(s, needle), s_in_range);
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &current_match);
+            // The index is incremented as long as the needle is not found.
+            self.integer_key.add_assign_parallelized(
+                &mut index,
+                &self.bool_to_radix(&self.integer_key.boolean_bitnot(&found)),
+            );
+        }
+        (found, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn decrypt_parts(parts: &[crate::ciphertext::FheString]) -> Vec<String> {
+        parts
+            .iter()
+            .map(|s| CLIENT_KEY.decrypt_string(s).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_strings() {
+        let clear_strings = vec!["bc", "", "ab", "b", "abc"];
+        let encrypted_strings: Vec<_> = clear_strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| CLIENT_KEY.encrypt_str_random_padding(s, i % 2).unwrap())
+            .collect();
+        let sorted = SERVER_KEY.sort_strings(&encrypted_strings);
+        let mut std_sorted = clear_strings.clone();
+        std_sorted.sort();
+        assert_eq!(decrypt_parts(&sorted), std_sorted);
+    }
+
+    #[test]
+    fn test_sort_split() {
+        let encrypted_s = CLIENT_KEY.encrypt_str("c b a").unwrap();
+        let fhe_split = SERVER_KEY.split(&encrypted_s, &' ');
+        let sorted = SERVER_KEY.sort_split(&fhe_split);
+        let number_parts = CLIENT_KEY.decrypt_integer(&sorted.number_parts) as usize;
+        assert_eq!(
+            decrypt_parts(&sorted.parts[..number_parts]),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_dedup() {
+        let clear_strings = vec!["a", "a", "b", "a", "a"];
+        let encrypted_strings: Vec<_> = clear_strings
+            .iter()
+            .map(|s| CLIENT_KEY.encrypt_str_padding(s, 1).unwrap())
+            .collect();
+        let deduped = SERVER_KEY.dedup(&encrypted_strings);
+        let number_parts = CLIENT_KEY.decrypt_integer(&deduped.number_parts) as usize;
+        let mut std_deduped = clear_strings.clone();
+        std_deduped.dedup();
+        assert_eq!(decrypt_parts(&deduped.parts[..number_parts]), std_deduped);
+    }
+
+    #[test]
+    fn test_contains_string_in_and_index_of() {
+        let encrypted_strings: Vec<_> = ["ab", "cd", "cd"]
+            .iter()
+            .map(|s| CLIENT_KEY.encrypt_str(s).unwrap())
+            .collect();
+        let encrypted_needle = CLIENT_KEY.encrypt_str_random_padding("cd", 2).unwrap();
+
+        let contains = SERVER_KEY.contains_string_in(&encrypted_strings, &encrypted_needle);
+        assert!(CLIENT_KEY.decrypt_bool(&contains));
+        let contains = SERVER_KEY.contains_string_in(&encrypted_strings, &"ef");
+        assert!(!CLIENT_KEY.decrypt_bool(&contains));
+
+        let (found, index) = SERVER_KEY.index_of(&encrypted_strings, &encrypted_needle);
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 1);
+    }
+
+    #[test]
+    fn test_index_of_in_split() {
+        let encrypted_s = CLIENT_KEY.encrypt_str("a,b").unwrap();
+        let fhe_split = SERVER_KEY.split(&encrypted_s, &',');
+        let (found, index) = SERVER_KEY.index_of_in_split(&fhe_split, &"b");
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 1);
+
+        // Parts after `number_parts` are ignored.
+        let contains_empty = SERVER_KEY.contains_string_in_split(&fhe_split, &"");
+        assert!(!CLIENT_KEY.decrypt_bool(&contains_empty));
+    }
+}
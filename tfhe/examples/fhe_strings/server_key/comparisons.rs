@@ -3,6 +3,14 @@ use crate::pattern::FhePattern;
 use crate::server_key::StringServerKey;
 use tfhe::integer::BooleanBlock;
 
+/// An encrypted `std::cmp::Ordering`. Exactly one of the three fields is an encryption of true.
+#[derive(Clone)]
+pub struct FheOrdering {
+    pub less: BooleanBlock,
+    pub equal: BooleanBlock,
+    pub greater: BooleanBlock,
+}
+
 impl StringServerKey {
     /// Check if the string encrypted by s1 is the same as pattern. Works with pattern clear string
     /// or encrypted string. Return an encrypted boolean
@@ -195,17 +203,13 @@ impl StringServerKey {
         if s1.content.len() > s2.content.len() {
             return self.integer_key.boolean_bitand(
                 &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s1.content[s2.content.len()].0, 0),
+                &self.eq_clear_char(&s1.content[s2.content.len()], 0),
             );
         }
         if s2.content.len() > s1.content.len() {
             return self.integer_key.boolean_bitand(
                 &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s2.content[s1.content.len()].0, 0),
+                &self.eq_clear_char(&s2.content[s1.content.len()], 0),
             );
         }
         result
@@ -228,17 +232,13 @@ impl StringServerKey {
         if s1.content.len() > s2.content.len() {
             return self.integer_key.boolean_bitand(
                 &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s1.content[s2.content.len()].0, 0),
+                &self.eq_clear_char(&s1.content[s2.content.len()], 0),
             );
         }
         if s2.content.len() > s1.content.len() {
             return self.integer_key.boolean_bitand(
                 &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s2.content[s1.content.len()].0, 0),
+                &self.eq_clear_char(&s2.content[s1.content.len()], 0),
             );
         }
         result
@@ -260,12 +260,9 @@ impl StringServerKey {
             )
         }
         if s1.content.len() > s2.len() {
-            return self.integer_key.boolean_bitand(
-                &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s1.content[s2.len()].0, 0),
-            );
+            return self
+                .integer_key
+                .boolean_bitand(&result, &self.eq_clear_char(&s1.content[s2.len()], 0));
         }
         result
     }
@@ -282,12 +279,9 @@ impl StringServerKey {
             )
         }
         if s1.content.len() > s2.len() {
-            return self.integer_key.boolean_bitand(
-                &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&s1.content[s2.len()].0, 0),
-            );
+            return self
+                .integer_key
+                .boolean_bitand(&result, &self.eq_clear_char(&s1.content[s2.len()], 0));
         }
         result
     }
@@ -324,6 +318,239 @@ impl StringServerKey {
         self.compare_clear(s1, s2, std::cmp::Ordering::Greater)
     }
 
+    /// Less than (<).
+    /// Checks if the string encrypted by s1 is strictly less than the pattern (clear or encrypted
+    /// string). The order is the lexicographic order for bytes. Returns false if the pattern is an
+    /// FheCharPatterns. Returns an encrypted value of 1 for true and an encrypted value of 0 for
+    /// false.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("abc").unwrap();
+    /// let encrypted_str2 = client_key.encrypt_str("abc").unwrap();
+    /// let result = server_key.lt(&encrypted_str, &encrypted_str2);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 0);
+    /// ```
+    pub fn lt(&self, s: &FheString, pattern: &impl FhePattern) -> BooleanBlock {
+        // The permutation lt <-> gt is to be consistent with argument order
+        pattern.gt_string(self, s)
+    }
+
+    /// Greater than (>).
+    /// Checks if the string encrypted by s1 is strictly greater than the pattern (clear or
+    /// encrypted string). The order is the lexicographic order for bytes. Returns false if the
+    /// pattern is an FheCharPatterns. Returns an encrypted value of 1 for true and an encrypted
+    /// value of 0 for false.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("abd").unwrap();
+    /// let result = server_key.gt(&encrypted_str, &"abc");
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn gt(&self, s: &FheString, pattern: &impl FhePattern) -> BooleanBlock {
+        // The permutation lt <-> gt is to be consistent with argument order
+        pattern.lt_string(self, s)
+    }
+
+    /// Less than (<).
+    /// Checks if the string encrypted by s1 is strictly less than the string encrypted by s2.
+    /// The order is the lexicographic order for bytes.
+    /// Return an encrypted value of 1 for true and an encrypted value of 0 for false.
+    pub fn lt_encrypted(&self, s1: &FheString, s2: &FheString) -> BooleanBlock {
+        self.cmp(s1, s2).less
+    }
+
+    /// Greater than (>).
+    /// Checks if the string encrypted by s1 is strictly greater than the string encrypted by s2.
+    /// The order is the lexicographic order for bytes.
+    /// Return an encrypted value of 1 for true and an encrypted value of 0 for false.
+    pub fn gt_encrypted(&self, s1: &FheString, s2: &FheString) -> BooleanBlock {
+        self.cmp(s1, s2).greater
+    }
+
+    /// Less than (<) clear.
+    /// Checks if the string encrypted by s1 is strictly less than the clear string s2.
+    /// The order is the lexicographic order for bytes.
+    /// Return an encrypted value of 1 for true and an encrypted value of 0 for false.
+    pub fn lt_clear(&self, s1: &FheString, s2: &str) -> BooleanBlock {
+        self.cmp_clear(s1, s2).less
+    }
+
+    /// Greater than (>) clear.
+    /// Checks if the string encrypted by s1 is strictly greater than the clear string s2.
+    /// The order is the lexicographic order for bytes.
+    /// Return an encrypted value of 1 for true and an encrypted value of 0 for false.
+    pub fn gt_clear(&self, s1: &FheString, s2: &str) -> BooleanBlock {
+        self.cmp_clear(s1, s2).greater
+    }
+
+    /// Three-way comparison of the encrypted strings s1 and s2 for the lexicographic order for
+    /// bytes. Returns an `FheOrdering`, computed in a single sweep over the content of the
+    /// strings.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("abc").unwrap();
+    /// let encrypted_str2 = client_key.encrypt_str_padding("abd", 2).unwrap();
+    /// let result = server_key.cmp(&encrypted_str, &encrypted_str2);
+    /// assert_eq!(client_key.decrypt_ordering(&result), std::cmp::Ordering::Less);
+    /// ```
+    pub fn cmp(&self, s1: &FheString, s2: &FheString) -> FheOrdering {
+        match (s1.padding, s2.padding) {
+            (Padding::None | Padding::Final, Padding::None | Padding::Final) => {
+                self.cmp_no_init_padding(s1, s2)
+            }
+            (Padding::None | Padding::Final, _) => {
+                self.cmp_no_init_padding(s1, &self.push_padding_to_end(s2))
+            }
+            (_, Padding::None | Padding::Final) => {
+                self.cmp_no_init_padding(&self.push_padding_to_end(s1), s2)
+            }
+            _ => self
+                .cmp_no_init_padding(&self.push_padding_to_end(s1), &self.push_padding_to_end(s2)),
+        }
+    }
+
+    /// Three-way comparison of the encrypted string s1 and the clear string s2 for the
+    /// lexicographic order for bytes.
+    pub fn cmp_clear(&self, s1: &FheString, s2: &str) -> FheOrdering {
+        match s1.padding {
+            Padding::None | Padding::Final => self.cmp_clear_no_init_padding(s1, s2),
+            _ => self.cmp_clear_no_init_padding(&self.push_padding_to_end(s1), s2),
+        }
+    }
+
+    /// Returns the smallest of the two encrypted strings s1 and s2 for the lexicographic order for
+    /// bytes. The result has final padding and an encrypted length.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("b").unwrap();
+    /// let encrypted_str2 = client_key.encrypt_str("abc").unwrap();
+    /// let result = server_key.min(&encrypted_str, &encrypted_str2);
+    /// assert_eq!(client_key.decrypt_string(&result).unwrap(), "abc");
+    /// ```
+    pub fn min(&self, s1: &FheString, s2: &FheString) -> FheString {
+        let content_length = std::cmp::max(s1.content.len(), s2.content.len());
+        let padded_s1 = self.pad_to_content_length(s1, content_length);
+        let padded_s2 = self.pad_to_content_length(s2, content_length);
+        let ordering = self.cmp_no_init_padding(&padded_s1, &padded_s2);
+        self.cmux_same_content_length(&ordering.less, &padded_s1, &padded_s2)
+    }
+
+    /// Returns the greatest of the two encrypted strings s1 and s2 for the lexicographic order for
+    /// bytes. The result has final padding and an encrypted length.
+    pub fn max(&self, s1: &FheString, s2: &FheString) -> FheString {
+        let content_length = std::cmp::max(s1.content.len(), s2.content.len());
+        let padded_s1 = self.pad_to_content_length(s1, content_length);
+        let padded_s2 = self.pad_to_content_length(s2, content_length);
+        let ordering = self.cmp_no_init_padding(&padded_s1, &padded_s2);
+        self.cmux_same_content_length(&ordering.greater, &padded_s1, &padded_s2)
+    }
+
+    /// Implementation of cmp, for FheString without initial padding zeros. The result is less if
+    /// the first character where the strings differ is smaller in s1. If one string is a prefix of
+    /// the other, the first padding zero (or the end of the content) decides.
+    pub fn cmp_no_init_padding(&self, s1: &FheString, s2: &FheString) -> FheOrdering {
+        let mut less = self.create_false();
+        let mut equal_up_to_n = self.create_true();
+        let min_length = std::cmp::min(s1.content.len(), s2.content.len());
+        for n in 0..min_length {
+            let char_equal = self.eq_char(&s1.content[n], &s2.content[n]);
+            let char_less = self.lt_char(&s1.content[n], &s2.content[n]);
+            // Only the first difference between the strings is taken into account.
+            let first_difference = self.integer_key.boolean_bitand(
+                &equal_up_to_n,
+                &self.integer_key.boolean_bitnot(&char_equal),
+            );
+            self.integer_key.boolean_bitor_assign(
+                &mut less,
+                &self
+                    .integer_key
+                    .boolean_bitand(&first_difference, &char_less),
+            );
+            self.integer_key
+                .boolean_bitand_assign(&mut equal_up_to_n, &char_equal);
+        }
+        let equal = if s1.content.len() > min_length {
+            self.integer_key.boolean_bitand(
+                &equal_up_to_n,
+                &self.eq_clear_char(&s1.content[min_length], 0),
+            )
+        } else if s2.content.len() > min_length {
+            let s2_ended = self.eq_clear_char(&s2.content[min_length], 0);
+            self.integer_key.boolean_bitor_assign(
+                &mut less,
+                &self
+                    .integer_key
+                    .boolean_bitand(&equal_up_to_n, &self.integer_key.boolean_bitnot(&s2_ended)),
+            );
+            self.integer_key.boolean_bitand(&equal_up_to_n, &s2_ended)
+        } else {
+            equal_up_to_n
+        };
+        self.ordering_from_less_and_equal(less, equal)
+    }
+
+    /// Implementation of cmp_clear, for FheString without initial padding zeros.
+    pub fn cmp_clear_no_init_padding(&self, s1: &FheString, s2: &str) -> FheOrdering {
+        let mut less = self.create_false();
+        let mut equal_up_to_n = self.create_true();
+        let min_length = std::cmp::min(s1.content.len(), s2.len());
+        for n in 0..min_length {
+            let char_equal = self.eq_clear_char(&s1.content[n], s2.as_bytes()[n]);
+            let char_less = self.lt_clear_char(&s1.content[n], s2.as_bytes()[n]);
+            // Only the first difference between the strings is taken into account.
+            let first_difference = self.integer_key.boolean_bitand(
+                &equal_up_to_n,
+                &self.integer_key.boolean_bitnot(&char_equal),
+            );
+            self.integer_key.boolean_bitor_assign(
+                &mut less,
+                &self
+                    .integer_key
+                    .boolean_bitand(&first_difference, &char_less),
+            );
+            self.integer_key
+                .boolean_bitand_assign(&mut equal_up_to_n, &char_equal);
+        }
+        let equal = if s1.content.len() > min_length {
+            self.integer_key.boolean_bitand(
+                &equal_up_to_n,
+                &self.eq_clear_char(&s1.content[min_length], 0),
+            )
+        } else if s2.len() > min_length {
+            // The clear string is longer than the content of s1, s1 is a strict prefix of s2 or
+            // differs before.
+            self.integer_key
+                .boolean_bitor_assign(&mut less, &equal_up_to_n);
+            self.create_false()
+        } else {
+            equal_up_to_n
+        };
+        self.ordering_from_less_and_equal(less, equal)
+    }
+
+    /// Builds an `FheOrdering` from encryptions of `less` and `equal`, `greater` being true if
+    /// both are false.
+    fn ordering_from_less_and_equal(&self, less: BooleanBlock, equal: BooleanBlock) -> FheOrdering {
+        let greater = self
+            .integer_key
+            .boolean_bitnot(&self.integer_key.boolean_bitor(&less, &equal));
+        FheOrdering {
+            less,
+            equal,
+            greater,
+        }
+    }
+
     /// Compares the encrypted strings for the lexicographic order for bytes.
     /// Return an encrypted value of 1 for true and an encrypted value of 0 for false.
     ///  -If the operator is std::cmp::Ordering::Less,
@@ -416,9 +643,7 @@ impl StringServerKey {
                     &result,
                     &self.integer_key.boolean_bitand(
                         &equal_up_to_n,
-                        &self
-                            .integer_key
-                            .scalar_eq_parallelized(&s1.content[s2.content.len()].0, 0),
+                        &self.eq_clear_char(&s1.content[s2.content.len()], 0),
                     ),
                 ),
             };
@@ -430,9 +655,7 @@ impl StringServerKey {
                     &result,
                     &self.integer_key.boolean_bitand(
                         &equal_up_to_n,
-                        &self
-                            .integer_key
-                            .scalar_eq_parallelized(&s2.content[s1.content.len()].0, 0),
+                        &self.eq_clear_char(&s2.content[s1.content.len()], 0),
                     ),
                 ),
             };
@@ -482,9 +705,7 @@ impl StringServerKey {
                     &result,
                     &self.integer_key.boolean_bitand(
                         &equal_up_to_n,
-                        &self
-                            .integer_key
-                            .scalar_eq_parallelized(&s1.content[s2.len()].0, 0),
+                        &self.eq_clear_char(&s1.content[s2.len()], 0),
                     ),
                 ),
             };
@@ -514,6 +735,14 @@ impl StringServerKey {
         c2: &FheAsciiChar,
         operator: std::cmp::Ordering,
     ) -> BooleanBlock {
+        // Clear characters of hybrid strings are compared with scalar operations.
+        match (c1.clear_value(), c2.clear_value()) {
+            (_, Some(clear_c2)) => return self.compare_clear_char(c1, clear_c2, operator),
+            (Some(clear_c1), None) => {
+                return self.compare_clear_char(c2, clear_c1, operator.reverse())
+            }
+            (None, None) => (),
+        }
         match operator {
             std::cmp::Ordering::Equal => self.integer_key.eq_parallelized(&c1.0, &c2.0),
             std::cmp::Ordering::Less => self.integer_key.le_parallelized(&c1.0, &c2.0),
@@ -522,11 +751,62 @@ impl StringServerKey {
     }
 
     pub fn eq_char(&self, c1: &FheAsciiChar, c2: &FheAsciiChar) -> BooleanBlock {
-        self.integer_key.eq_parallelized(&c1.0, &c2.0)
+        self.compare_char(c1, c2, std::cmp::Ordering::Equal)
     }
 
     pub fn eq_clear_char(&self, c1: &FheAsciiChar, c2: u8) -> BooleanBlock {
-        self.integer_key.scalar_eq_parallelized(&c1.0, c2)
+        self.compare_clear_char(c1, c2, std::cmp::Ordering::Equal)
+    }
+
+    pub fn ne_clear_char(&self, c: &FheAsciiChar, scalar: u8) -> BooleanBlock {
+        match c.clear_value() {
+            Some(clear_c) => self.create_bool(clear_c != scalar),
+            None => self.integer_key.scalar_ne_parallelized(&c.0, scalar),
+        }
+    }
+
+    /// Returns true if the character encrypted by c1 is strictly less than the character encrypted
+    /// by c2.
+    pub fn lt_char(&self, c1: &FheAsciiChar, c2: &FheAsciiChar) -> BooleanBlock {
+        match (c1.clear_value(), c2.clear_value()) {
+            (_, Some(clear_c2)) => self.lt_clear_char(c1, clear_c2),
+            (Some(clear_c1), None) => self.integer_key.scalar_gt_parallelized(&c2.0, clear_c1),
+            (None, None) => self.integer_key.lt_parallelized(&c1.0, &c2.0),
+        }
+    }
+
+    /// Returns true if the character encrypted by c is strictly less than the clear character
+    /// `scalar`.
+    pub fn lt_clear_char(&self, c: &FheAsciiChar, scalar: u8) -> BooleanBlock {
+        match c.clear_value() {
+            Some(clear_c) => self.create_bool(clear_c < scalar),
+            None => self.integer_key.scalar_lt_parallelized(&c.0, scalar),
+        }
+    }
+
+    /// Returns if_true if condition encrypts true and if_false otherwise. The selection is done in
+    /// the clear if condition is a trivial encryption or if both characters are the same clear
+    /// character.
+    pub fn cmux_char(
+        &self,
+        condition: &BooleanBlock,
+        if_true: &FheAsciiChar,
+        if_false: &FheAsciiChar,
+    ) -> FheAsciiChar {
+        if let Ok(clear_condition) = condition.decrypt_trivial() {
+            return match clear_condition {
+                true => if_true.clone(),
+                false => if_false.clone(),
+            };
+        }
+        match (if_true.clear_value(), if_false.clear_value()) {
+            (Some(c1), Some(c2)) if c1 == c2 => if_true.clone(),
+            _ => FheAsciiChar(self.integer_key.cmux_parallelized(
+                condition,
+                &if_true.0,
+                &if_false.0,
+            )),
+        }
     }
 
     /// Compares the encrypted character c1 and the clear char c2 with the operator `operator`.
@@ -544,6 +824,13 @@ impl StringServerKey {
         scalar: u8,
         operator: std::cmp::Ordering,
     ) -> BooleanBlock {
+        if let Some(clear_c) = c.clear_value() {
+            return self.create_bool(match operator {
+                std::cmp::Ordering::Equal => clear_c == scalar,
+                std::cmp::Ordering::Less => clear_c <= scalar,
+                std::cmp::Ordering::Greater => clear_c >= scalar,
+            });
+        }
         match operator {
             std::cmp::Ordering::Equal => self.integer_key.scalar_eq_parallelized(&c.0, scalar),
             std::cmp::Ordering::Less => self.integer_key.scalar_le_parallelized(&c.0, scalar),
@@ -606,6 +893,50 @@ mod tests {
     //     assert_eq!(decrypted_string, "b");
     // }
 
+    #[test]
+    fn test_cmp() {
+        let pairs = [
+            ("abc", "abd"),
+            ("ab", "abc"),
+            ("abc", "ab"),
+            ("abc", "abc"),
+            ("", ""),
+        ];
+        for (clear_s1, clear_s2) in pairs {
+            let s1 = CLIENT_KEY.encrypt_str_random_padding(clear_s1, 1).unwrap();
+            let s2 = CLIENT_KEY.encrypt_str_padding(clear_s2, 2).unwrap();
+            let result = SERVER_KEY.cmp(&s1, &s2);
+            assert_eq!(CLIENT_KEY.decrypt_ordering(&result), clear_s1.cmp(clear_s2));
+            let result_clear = SERVER_KEY.cmp_clear(&s1, clear_s2);
+            assert_eq!(
+                CLIENT_KEY.decrypt_ordering(&result_clear),
+                clear_s1.cmp(clear_s2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_lt_gt() {
+        let s1 = CLIENT_KEY.encrypt_str("ab").unwrap();
+        let s2 = CLIENT_KEY.encrypt_str_padding("ab", 1).unwrap();
+        let lt = SERVER_KEY.lt(&s1, &s2);
+        assert!(!CLIENT_KEY.decrypt_bool(&lt));
+        let gt = SERVER_KEY.gt(&s1, &"a");
+        assert!(CLIENT_KEY.decrypt_bool(&gt));
+        let lt_clear = SERVER_KEY.lt(&s1, &"b");
+        assert!(CLIENT_KEY.decrypt_bool(&lt_clear));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let s1 = CLIENT_KEY.encrypt_str_random_padding("b", 2).unwrap();
+        let s2 = CLIENT_KEY.encrypt_str("abc").unwrap();
+        let min = SERVER_KEY.min(&s1, &s2);
+        assert_eq!(CLIENT_KEY.decrypt_string(&min).unwrap(), "abc");
+        let max = SERVER_KEY.max(&s1, &s2);
+        assert_eq!(CLIENT_KEY.decrypt_string(&max).unwrap(), "b");
+    }
+
     #[test]
     fn test_pop_last_non_zero_char() {
         let mut encrypted_str = CLIENT_KEY
@@ -1,5 +1,5 @@
 use crate::ciphertext::{FheAsciiChar, FheString, Padding};
-use crate::pattern::FhePattern;
+use crate::pattern::{FhePattern, FheSlicePattern};
 use crate::server_key::StringServerKey;
 use tfhe::integer::BooleanBlock;
 
@@ -113,9 +113,7 @@ impl StringServerKey {
                     }
                     _ => self.integer_key.boolean_bitor(
                         &self.compare_char(c, &prefix.content[n], std::cmp::Ordering::Equal),
-                        &self
-                            .integer_key
-                            .scalar_eq_parallelized(&prefix.content[n].0, 0),
+                        &self.eq_clear_char(&prefix.content[n], 0),
                     ),
                 },
             )
@@ -123,12 +121,9 @@ impl StringServerKey {
         // If prefix content size is greater than s content size, check if the extra characters are
         // padding zeros
         if prefix.content.len() > s.len() {
-            return self.integer_key.boolean_bitand(
-                &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&prefix.content[s.len()].0, 0),
-            );
+            return self
+                .integer_key
+                .boolean_bitand(&result, &self.eq_clear_char(&prefix.content[s.len()], 0));
         }
         result
     }
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tfhe::integer::ServerKey;
+
+/// The integer server key of a `StringServerKey`, counting the homomorphic operations performed
+/// with it. Every call to a method of the integer key goes through `Deref`, which increments the
+/// counter. The count is a number of integer operations, each of them performing one or several
+/// PBS, like `Execution::ct_operations_count` in the regex engine example.
+pub struct CountingServerKey {
+    key: ServerKey,
+    operations: AtomicUsize,
+}
+
+impl CountingServerKey {
+    /// Number of integer operations performed since the creation of the key or the last reset.
+    pub fn operations_count(&self) -> usize {
+        self.operations.load(Ordering::Relaxed)
+    }
+
+    pub fn reset_operations_count(&self) {
+        self.operations.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Deref for CountingServerKey {
+    type Target = ServerKey;
+
+    fn deref(&self) -> &ServerKey {
+        self.operations.fetch_add(1, Ordering::Relaxed);
+        &self.key
+    }
+}
+
+impl From<ServerKey> for CountingServerKey {
+    fn from(key: ServerKey) -> Self {
+        Self {
+            key,
+            operations: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// A clone starts counting from zero.
+impl Clone for CountingServerKey {
+    fn clone(&self) -> Self {
+        Self::from(self.key.clone())
+    }
+}
+
+/// The counter is not serialized, a `CountingServerKey` is serialized as its integer key.
+impl Serialize for CountingServerKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.key.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CountingServerKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ServerKey::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_operations_count() {
+        let server_key = SERVER_KEY.clone();
+        assert_eq!(server_key.integer_key.operations_count(), 0);
+        let encrypted_str = CLIENT_KEY.encrypt_str("ab").unwrap();
+        let lowercase = server_key.to_lowercase(&encrypted_str);
+        assert!(server_key.integer_key.operations_count() > 0);
+        assert_eq!(CLIENT_KEY.decrypt_string(&lowercase).unwrap(), "ab");
+
+        server_key.integer_key.reset_operations_count();
+        let serialized = bincode::serialize(&server_key).unwrap();
+        let deserialized: StringServerKey = bincode::deserialize(&serialized).unwrap();
+        let uppercase = deserialized.to_uppercase(&encrypted_str);
+        assert_eq!(CLIENT_KEY.decrypt_string(&uppercase).unwrap(), "AB");
+        assert_eq!(server_key.integer_key.operations_count(), 0);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::pattern::FheCharPattern;
+use crate::server_key::split::FheSplit;
+use crate::server_key::StringServerKey;
+use tfhe::integer::BooleanBlock;
+
+impl StringServerKey {
+    /// Splits the CSV line s in fields separated by `delimiter`. A field can be enclosed in
+    /// `quote` characters, then the delimiters it contains do not end the field, the enclosing
+    /// quotes are removed and doubled quotes are unescaped into one quote. As in RFC 4180, a quote
+    /// opens a quoted part only at the start of a field, elsewhere it is kept as a character. Only
+    /// the first `max_fields` fields are returned, the following ones are ignored.
+    /// The parsing is driven by an encrypted state updated at each character of s, made of three
+    /// bits: start of a field, inside quotes, and quote just seen inside quotes (the end of the
+    /// quoted part or the first half of an escaped quote). Padding zeros are skipped and leave the
+    /// state unchanged, so s can have any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str(r#"1,"a, ""b""",c"#).unwrap();
+    /// let fields = server_key.parse_csv_line(&encrypted_str, &',', &'"', 4);
+    /// assert_eq!(client_key.decrypt_integer(&fields.number_parts), 3);
+    /// let clear_field = client_key.decrypt_string(&fields.parts[1]).unwrap();
+    /// assert_eq!(clear_field, r#"a, "b""#);
+    /// ```
+    pub fn parse_csv_line(
+        &self,
+        s: &FheString,
+        delimiter: &impl FheCharPattern,
+        quote: &impl FheCharPattern,
+        max_fields: usize,
+    ) -> FheSplit {
+        let zero = FheAsciiChar(self.create_zero());
+        let mut field_start = self.create_true();
+        let mut inside_quotes = self.create_false();
+        let mut quote_seen = self.create_false();
+        let mut field_index = self.create_zero();
+        let mut contents: Vec<Vec<FheAsciiChar>> = vec![Vec::new(); max_fields];
+        let mut lengths = vec![self.create_zero(); max_fields];
+
+        for c in &s.content {
+            let is_char = self.ne_clear_char(c, 0);
+            let is_quote = quote.fhe_eq(self, c);
+            let is_delimiter = delimiter.fhe_eq(self, c);
+            // Inside quotes and not just after a quote.
+            let in_quoted_text = self.integer_key.boolean_bitand(
+                &inside_quotes,
+                &self.integer_key.boolean_bitnot(&quote_seen),
+            );
+
+            let ends_field = self.integer_key.boolean_bitand(
+                &self.integer_key.boolean_bitand(&is_char, &is_delimiter),
+                &self.integer_key.boolean_bitnot(&in_quoted_text),
+            );
+            // Quotes opening a quoted part, ending it or escaping a quote are not kept.
+            let quote_opens_or_ends = self.integer_key.boolean_bitand(
+                &is_quote,
+                &self
+                    .integer_key
+                    .boolean_bitor(&field_start, &in_quoted_text),
+            );
+            let kept = self.integer_key.boolean_bitand(
+                &self
+                    .integer_key
+                    .boolean_bitand(&is_char, &self.integer_key.boolean_bitnot(&ends_field)),
+                &self.integer_key.boolean_bitnot(&quote_opens_or_ends),
+            );
+
+            for (k, (content, length)) in contents.iter_mut().zip(lengths.iter_mut()).enumerate() {
+                let in_field = self.integer_key.boolean_bitand(
+                    &kept,
+                    &self
+                        .integer_key
+                        .scalar_eq_parallelized(&field_index, k as u64),
+                );
+                content.push(self.cmux_char(&in_field, c, &zero));
+                self.integer_key
+                    .add_assign_parallelized(length, &self.bool_to_radix(&in_field));
+            }
+
+            self.integer_key
+                .add_assign_parallelized(&mut field_index, &self.bool_to_radix(&ends_field));
+            // A quote opens a quoted part at the start of a field, and reopens it just after its end
+            // as the second half of an escaped quote.
+            let quote_enters_quotes = self.integer_key.boolean_bitand(
+                &is_quote,
+                &self.integer_key.boolean_bitor(&field_start, &inside_quotes),
+            );
+            inside_quotes = self.update_state(
+                &is_char,
+                &self
+                    .integer_key
+                    .boolean_bitor(&in_quoted_text, &quote_enters_quotes),
+                &inside_quotes,
+            );
+            quote_seen = self.update_state(
+                &is_char,
+                &self.integer_key.boolean_bitand(&in_quoted_text, &is_quote),
+                &quote_seen,
+            );
+            field_start = self.update_state(&is_char, &ends_field, &field_start);
+        }
+
+        let parts = contents
+            .into_iter()
+            .zip(lengths)
+            .map(|(content, length)| FheString {
+                content,
+                padding: Padding::Anywhere,
+                length: FheStrLength::Encrypted(length),
+            })
+            .collect();
+        FheSplit {
+            parts,
+            number_parts: self.integer_key.scalar_min_parallelized(
+                &self.integer_key.scalar_add_parallelized(&field_index, 1),
+                max_fields as u64,
+            ),
+            current_index: 0,
+        }
+    }
+
+    /// Returns `new_state` if `is_char` is true, `state` otherwise.
+    pub fn update_state(
+        &self,
+        is_char: &BooleanBlock,
+        new_state: &BooleanBlock,
+        state: &BooleanBlock,
+    ) -> BooleanBlock {
+        self.integer_key.boolean_bitor(
+            &self.integer_key.boolean_bitand(is_char, new_state),
+            &self
+                .integer_key
+                .boolean_bitand(&self.integer_key.boolean_bitnot(is_char), state),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_parse_csv_line(s: &str, padding: usize, max_fields: usize, expected: &[&str]) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let encrypted_delimiter = CLIENT_KEY.encrypt_ascii_char(b';');
+        let fields =
+            SERVER_KEY.parse_csv_line(&encrypted_s, &encrypted_delimiter, &'\'', max_fields);
+        let number_parts = CLIENT_KEY.decrypt_integer(&fields.number_parts) as usize;
+        let clear_fields: Vec<String> = fields.parts[..number_parts]
+            .iter()
+            .map(|field| CLIENT_KEY.decrypt_string(field).unwrap())
+            .collect();
+        assert_eq!(clear_fields, expected);
+    }
+
+    #[test]
+    fn test_parse_csv_line() {
+        check_parse_csv_line("a;'b;c';d", 1, 4, &["a", "b;c", "d"]);
+        check_parse_csv_line("'x''y';;z", 2, 4, &["x'y", "", "z"]);
+        check_parse_csv_line("''", 0, 2, &[""]);
+        check_parse_csv_line("", 1, 2, &[""]);
+        check_parse_csv_line("a;b;c", 0, 2, &["a", "b"]);
+        // A quote in the middle of an unquoted field does not open a quoted part.
+        check_parse_csv_line("a'b;c'", 1, 3, &["a'b", "c'"]);
+    }
+}
@@ -0,0 +1,523 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::server_key::strip::FheOptionString;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+impl StringServerKey {
+    /// Returns the hexadecimal encoding (with lowercase digits) of the bytes of `s`. Each
+    /// character is encoded by two characters and each padding zero by two padding zeros, so the
+    /// result has the same kind of padding as `s`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("Hi!").unwrap();
+    /// let result = server_key.to_hex(&encrypted_str);
+    /// let clear_result = client_key.decrypt_string(&result).unwrap();
+    /// assert_eq!(clear_result, "486921");
+    /// ```
+    pub fn to_hex(&self, s: &FheString) -> FheString {
+        let zero = FheAsciiChar(self.create_zero());
+        let mut content: Vec<FheAsciiChar> = Vec::with_capacity(2 * s.content.len());
+        for c in &s.content {
+            let is_null = self.eq_clear_char(c, 0);
+            let high_digit = self.integer_key.scalar_right_shift_parallelized(&c.0, 4u32);
+            let low_digit = self.integer_key.scalar_bitand_parallelized(&c.0, 15u8);
+            for digit in [high_digit, low_digit] {
+                content.push(self.cmux_char(&is_null, &zero, &self.hex_digit_to_char(&digit)));
+            }
+        }
+        let length = match &s.length {
+            FheStrLength::Clear(length) => FheStrLength::Clear(2 * length),
+            FheStrLength::Encrypted(length) => {
+                FheStrLength::Encrypted(self.integer_key.scalar_mul_parallelized(length, 2u8))
+            }
+        };
+        FheString {
+            content,
+            padding: s.padding,
+            length,
+        }
+    }
+
+    /// Decodes the hexadecimal string `s` (digits can be lowercase or uppercase). Returns an
+    /// encryption of (true, decoded) if `s` has an even length, contains only hexadecimal digits
+    /// and encodes non null ASCII characters. Returns an encryption of (false, _) otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("48692a").unwrap();
+    /// let (valid, result) = server_key.from_hex(&encrypted_str);
+    /// let clear_valid = client_key.decrypt_integer(&server_key.bool_to_radix(&valid));
+    /// assert_eq!(clear_valid, 1);
+    /// assert_eq!(client_key.decrypt_string(&result).unwrap(), "Hi*");
+    /// ```
+    // The name mirrors `to_hex`, even if the method takes `self`.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_hex(&self, s: &FheString) -> FheOptionString {
+        match s.padding {
+            Padding::None | Padding::Final => self.decode_hex_final_padding(s),
+            _ => self.decode_hex_final_padding(&self.push_padding_to_end(s)),
+        }
+    }
+
+    /// Returns the base64 encoding (standard alphabet, with `=` padding) of the bytes of `s`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str_padding("ab", 2).unwrap();
+    /// let result = server_key.base64_encode(&encrypted_str);
+    /// let clear_result = client_key.decrypt_string(&result).unwrap();
+    /// assert_eq!(clear_result, "YWI=");
+    /// ```
+    pub fn base64_encode(&self, s: &FheString) -> FheString {
+        match s.padding {
+            Padding::None | Padding::Final => self.base64_encode_final_padding(s),
+            _ => self.base64_encode_final_padding(&self.push_padding_to_end(s)),
+        }
+    }
+
+    /// Decodes the base64 string `s` (standard alphabet, with `=` padding). Returns an encryption
+    /// of (true, decoded) if `s` is a valid base64 string encoding non null ASCII characters.
+    /// Returns an encryption of (false, _) otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("YWI=").unwrap();
+    /// let (valid, result) = server_key.base64_decode(&encrypted_str);
+    /// let clear_valid = client_key.decrypt_integer(&server_key.bool_to_radix(&valid));
+    /// assert_eq!(clear_valid, 1);
+    /// assert_eq!(client_key.decrypt_string(&result).unwrap(), "ab");
+    /// ```
+    pub fn base64_decode(&self, s: &FheString) -> FheOptionString {
+        match s.padding {
+            Padding::None | Padding::Final => self.base64_decode_final_padding(s),
+            _ => self.base64_decode_final_padding(&self.push_padding_to_end(s)),
+        }
+    }
+
+    /// Implementation of from_hex for strings with at worst final padding. The digits are decoded
+    /// by pairs.
+    fn decode_hex_final_padding(&self, s: &FheString) -> FheOptionString {
+        let zero = FheAsciiChar(self.create_zero());
+        let mut valid = self.create_true();
+        let mut content: Vec<FheAsciiChar> = Vec::with_capacity((s.content.len() + 1) / 2);
+        for pair in s.content.chunks(2) {
+            let high = &pair[0];
+            // A missing last character is handled as a padding zero.
+            let low = pair.get(1).unwrap_or(&zero);
+            let (high_valid, high_digit) = self.hex_char_to_digit(high);
+            let (low_valid, low_digit) = self.hex_char_to_digit(low);
+            let high_is_null = self.eq_clear_char(high, 0);
+            let low_is_null = self.eq_clear_char(low, 0);
+
+            let byte = FheAsciiChar(
+                self.integer_key.bitor_parallelized(
+                    &self
+                        .integer_key
+                        .scalar_left_shift_parallelized(&high_digit, 4u32),
+                    &low_digit,
+                ),
+            );
+
+            // The pair is valid if it is padding or if it encodes a non null ASCII character.
+            let encodes_char = self.integer_key.boolean_bitand(
+                &self.integer_key.boolean_bitand(&high_valid, &low_valid),
+                &self.is_non_null_ascii(&byte),
+            );
+            let pair_is_padding = self.integer_key.boolean_bitand(&high_is_null, &low_is_null);
+            self.integer_key.boolean_bitand_assign(
+                &mut valid,
+                &self
+                    .integer_key
+                    .boolean_bitor(&encodes_char, &pair_is_padding),
+            );
+            content.push(self.cmux_char(&high_is_null, &zero, &byte));
+        }
+        let length = match &s.length {
+            FheStrLength::Clear(length) => FheStrLength::Clear(length / 2),
+            FheStrLength::Encrypted(length) => FheStrLength::Encrypted(
+                self.integer_key
+                    .scalar_right_shift_parallelized(length, 1u32),
+            ),
+        };
+        (
+            valid,
+            FheString {
+                content,
+                padding: s.padding,
+                length,
+            },
+        )
+    }
+
+    /// Implementation of base64_encode for strings with at worst final padding. Each group of 3
+    /// characters is encoded by 4 characters.
+    fn base64_encode_final_padding(&self, s: &FheString) -> FheString {
+        let zero = FheAsciiChar(self.create_zero());
+        let equal_sign = FheAsciiChar(self.create_n(b'='));
+        let mut content: Vec<FheAsciiChar> = Vec::with_capacity(4 * ((s.content.len() + 2) / 3));
+        let mut encrypted_length = self.create_zero();
+        for group in s.content.chunks(3) {
+            // Missing characters are handled as padding zeros.
+            let c0 = &group[0];
+            let c1 = group.get(1).unwrap_or(&zero);
+            let c2 = group.get(2).unwrap_or(&zero);
+            let present0 = self.ne_clear_char(c0, 0);
+            let present1 = self.ne_clear_char(c1, 0);
+            let present2 = self.ne_clear_char(c2, 0);
+            let (b0, b1, b2) = (&c0.0, &c1.0, &c2.0);
+
+            // Repack the 24 bits of the group in four 6 bits values.
+            let v0 = self.integer_key.scalar_right_shift_parallelized(b0, 2u32);
+            let v1 = self.integer_key.bitor_parallelized(
+                &self.integer_key.scalar_left_shift_parallelized(
+                    &self.integer_key.scalar_bitand_parallelized(b0, 3u8),
+                    4u32,
+                ),
+                &self.integer_key.scalar_right_shift_parallelized(b1, 4u32),
+            );
+            let v2 = self.integer_key.bitor_parallelized(
+                &self.integer_key.scalar_left_shift_parallelized(
+                    &self.integer_key.scalar_bitand_parallelized(b1, 15u8),
+                    2u32,
+                ),
+                &self.integer_key.scalar_right_shift_parallelized(b2, 6u32),
+            );
+            let v3 = self.integer_key.scalar_bitand_parallelized(b2, 63u8);
+
+            // Missing characters at the end of a non empty group are encoded by `=`.
+            let equal_sign_or_zero = self.cmux_char(&present0, &equal_sign, &zero);
+            content.push(self.cmux_char(&present0, &self.base64_value_to_char(&v0), &zero));
+            content.push(self.cmux_char(&present0, &self.base64_value_to_char(&v1), &zero));
+            content.push(self.cmux_char(
+                &present1,
+                &self.base64_value_to_char(&v2),
+                &equal_sign_or_zero,
+            ));
+            content.push(self.cmux_char(
+                &present2,
+                &self.base64_value_to_char(&v3),
+                &equal_sign_or_zero,
+            ));
+            self.integer_key.add_assign_parallelized(
+                &mut encrypted_length,
+                &self
+                    .integer_key
+                    .scalar_mul_parallelized(&self.bool_to_radix(&present0), 4u8),
+            );
+        }
+        let length = match &s.length {
+            FheStrLength::Clear(length) => FheStrLength::Clear(4 * ((length + 2) / 3)),
+            FheStrLength::Encrypted(_) => FheStrLength::Encrypted(encrypted_length),
+        };
+        FheString {
+            content,
+            padding: s.padding,
+            length,
+        }
+    }
+
+    /// Implementation of base64_decode for strings with at worst final padding. Each group of 4
+    /// characters is decoded in 3 characters.
+    fn base64_decode_final_padding(&self, s: &FheString) -> FheOptionString {
+        let zero = FheAsciiChar(self.create_zero());
+        let mut valid = self.create_true();
+        let mut content: Vec<FheAsciiChar> = Vec::with_capacity(3 * ((s.content.len() + 3) / 4));
+        let mut length = self.create_zero();
+        // `previous_group_padded` is true if the previous group ends with `=`, in which case the
+        // current group must be empty.
+        let mut previous_group_padded = self.create_false();
+        for group in s.content.chunks(4) {
+            // Missing characters are handled as padding zeros.
+            let chars: Vec<&FheAsciiChar> = (0..4).map(|i| group.get(i).unwrap_or(&zero)).collect();
+            let decoded: Vec<(BooleanBlock, RadixCiphertext)> =
+                chars.iter().map(|c| self.base64_char_to_value(c)).collect();
+            let is_null: Vec<BooleanBlock> =
+                chars.iter().map(|c| self.eq_clear_char(c, 0)).collect();
+            let is_equal_sign: Vec<BooleanBlock> =
+                chars.iter().map(|c| self.eq_clear_char(c, b'=')).collect();
+            let (v0, v1, v2, v3) = (&decoded[0].1, &decoded[1].1, &decoded[2].1, &decoded[3].1);
+
+            // Check the validity of the group, either empty or made of 2 to 4 base64 characters
+            // followed by `=`.
+            let empty_group = self.integer_key.boolean_bitand(
+                &self.integer_key.boolean_bitand(&is_null[0], &is_null[1]),
+                &self.integer_key.boolean_bitand(&is_null[2], &is_null[3]),
+            );
+            let ends_with_two_equal_signs = self
+                .integer_key
+                .boolean_bitand(&is_equal_sign[2], &is_equal_sign[3]);
+            let full_group = self.integer_key.boolean_bitand(
+                &self
+                    .integer_key
+                    .boolean_bitand(&decoded[0].0, &decoded[1].0),
+                &self.integer_key.boolean_bitand(
+                    &self
+                        .integer_key
+                        .boolean_bitor(&decoded[2].0, &ends_with_two_equal_signs),
+                    &self
+                        .integer_key
+                        .boolean_bitor(&decoded[3].0, &is_equal_sign[3]),
+                ),
+            );
+            let group_valid = self.integer_key.boolean_bitor(
+                &empty_group,
+                &self.integer_key.boolean_bitand(
+                    &full_group,
+                    &self.integer_key.boolean_bitnot(&previous_group_padded),
+                ),
+            );
+            self.integer_key
+                .boolean_bitand_assign(&mut valid, &group_valid);
+            previous_group_padded = is_equal_sign[3].clone();
+
+            // Repack the four 6 bits values in 3 bytes.
+            let b0 = FheAsciiChar(self.integer_key.bitor_parallelized(
+                &self.integer_key.scalar_left_shift_parallelized(v0, 2u32),
+                &self.integer_key.scalar_right_shift_parallelized(v1, 4u32),
+            ));
+            let b1 = FheAsciiChar(self.integer_key.bitor_parallelized(
+                &self.integer_key.scalar_left_shift_parallelized(
+                    &self.integer_key.scalar_bitand_parallelized(v1, 15u8),
+                    4u32,
+                ),
+                &self.integer_key.scalar_right_shift_parallelized(v2, 2u32),
+            ));
+            let b2 = FheAsciiChar(self.integer_key.bitor_parallelized(
+                &self.integer_key.scalar_left_shift_parallelized(
+                    &self.integer_key.scalar_bitand_parallelized(v2, 3u8),
+                    6u32,
+                ),
+                v3,
+            ));
+
+            let present0 = self.integer_key.boolean_bitnot(&is_null[0]);
+            let present1 = self.integer_key.boolean_bitand(
+                &present0,
+                &self.integer_key.boolean_bitnot(&is_equal_sign[2]),
+            );
+            let present2 = self.integer_key.boolean_bitand(
+                &present0,
+                &self.integer_key.boolean_bitnot(&is_equal_sign[3]),
+            );
+            for (byte, present) in [(b0, present0), (b1, present1), (b2, present2)] {
+                // Each present byte must be a non null ASCII character.
+                self.integer_key.boolean_bitand_assign(
+                    &mut valid,
+                    &self.integer_key.boolean_bitor(
+                        &self.integer_key.boolean_bitnot(&present),
+                        &self.is_non_null_ascii(&byte),
+                    ),
+                );
+                self.integer_key
+                    .add_assign_parallelized(&mut length, &self.bool_to_radix(&present));
+                content.push(self.cmux_char(&present, &byte, &zero));
+            }
+        }
+        let result = match content.len() {
+            0 => FheString {
+                content,
+                padding: Padding::None,
+                length: FheStrLength::Clear(0),
+            },
+            _ => FheString {
+                content,
+                padding: Padding::Final,
+                length: FheStrLength::Encrypted(length),
+            },
+        };
+        (valid, result)
+    }
+
+    /// Returns the ASCII character of the hexadecimal digit `digit`, which is assumed to be lower
+    /// than 16.
+    fn hex_digit_to_char(&self, digit: &RadixCiphertext) -> FheAsciiChar {
+        // Digits from 0 to 9 are mapped to '0'..='9' and digits from 10 to 15 to 'a'..='f'.
+        let is_letter = self.integer_key.scalar_gt_parallelized(digit, 9u8);
+        FheAsciiChar(
+            self.integer_key.add_parallelized(
+                &self.integer_key.scalar_add_parallelized(digit, b'0'),
+                &self
+                    .integer_key
+                    .scalar_mul_parallelized(&self.bool_to_radix(&is_letter), b'a' - b'0' - 10),
+            ),
+        )
+    }
+
+    /// Returns an encryption of (true, d) if `c` encrypts an hexadecimal digit of value `d`, and
+    /// an encryption of (false, _) otherwise.
+    fn hex_char_to_digit(&self, c: &FheAsciiChar) -> (BooleanBlock, RadixCiphertext) {
+        let is_digit = self.is_in_char_range(c, b'0', b'9');
+        let is_lowercase = self.is_in_char_range(c, b'a', b'f');
+        let is_uppercase = self.is_in_char_range(c, b'A', b'F');
+        let shifted =
+            |offset: u8| FheAsciiChar(self.integer_key.scalar_sub_parallelized(&c.0, offset));
+        let value = self.cmux_char(
+            &is_digit,
+            &shifted(b'0'),
+            &self.cmux_char(&is_lowercase, &shifted(b'a' - 10), &shifted(b'A' - 10)),
+        );
+        let valid = self.integer_key.boolean_bitor(
+            &is_digit,
+            &self.integer_key.boolean_bitor(&is_lowercase, &is_uppercase),
+        );
+        (valid, value.0)
+    }
+
+    /// Returns the base64 character of `value`, which is assumed to be lower than 64.
+    fn base64_value_to_char(&self, value: &RadixCiphertext) -> FheAsciiChar {
+        // The alphabet is made of 4 ranges, 'A'..='Z', 'a'..='z', '0'..='9' and '+', '/'. The
+        // offset between the value and its character changes at the start of each range.
+        let mut result = self.integer_key.scalar_add_parallelized(value, b'A');
+        // The offsets are +65, +71, -4, -19 and -16.
+        let offset_changes: [(u8, u8, bool); 4] = [
+            (26, 6, true),
+            (52, 75, false),
+            (62, 15, false),
+            (63, 3, true),
+        ];
+        for (threshold, offset_change, increase) in offset_changes {
+            let above_threshold =
+                self.bool_to_radix(&self.integer_key.scalar_ge_parallelized(value, threshold));
+            let offset = self
+                .integer_key
+                .scalar_mul_parallelized(&above_threshold, offset_change);
+            if increase {
+                self.integer_key
+                    .add_assign_parallelized(&mut result, &offset);
+            } else {
+                self.integer_key
+                    .sub_assign_parallelized(&mut result, &offset);
+            }
+        }
+        FheAsciiChar(result)
+    }
+
+    /// Returns an encryption of (true, v) if `c` encrypts a base64 character of value `v`, and an
+    /// encryption of (false, _) otherwise.
+    fn base64_char_to_value(&self, c: &FheAsciiChar) -> (BooleanBlock, RadixCiphertext) {
+        let is_uppercase = self.is_in_char_range(c, b'A', b'Z');
+        let is_lowercase = self.is_in_char_range(c, b'a', b'z');
+        let is_digit = self.is_in_char_range(c, b'0', b'9');
+        let is_plus = self.eq_clear_char(c, b'+');
+        let is_slash = self.eq_clear_char(c, b'/');
+        let shifted =
+            |offset: u8| FheAsciiChar(self.integer_key.scalar_sub_parallelized(&c.0, offset));
+        let value = self.cmux_char(
+            &is_uppercase,
+            &shifted(b'A'),
+            &self.cmux_char(
+                &is_lowercase,
+                &shifted(b'a' - 26),
+                &self.cmux_char(
+                    &is_digit,
+                    &FheAsciiChar(self.integer_key.scalar_add_parallelized(&c.0, 52 - b'0')),
+                    &self.cmux_char(
+                        &is_plus,
+                        &FheAsciiChar(self.create_n(62)),
+                        &FheAsciiChar(self.create_n(63)),
+                    ),
+                ),
+            ),
+        );
+        let valid = self.integer_key.boolean_bitor(
+            &self.integer_key.boolean_bitor(&is_uppercase, &is_lowercase),
+            &self.integer_key.boolean_bitor(
+                &is_digit,
+                &self.integer_key.boolean_bitor(&is_plus, &is_slash),
+            ),
+        );
+        (valid, value.0)
+    }
+
+    /// Checks if `c` is in the range `low..=high`, in the clear if `c` is a trivial encryption.
+    pub fn is_in_char_range(&self, c: &FheAsciiChar, low: u8, high: u8) -> BooleanBlock {
+        if let Some(clear_c) = c.clear_value() {
+            return self.create_bool((low..=high).contains(&clear_c));
+        }
+        self.integer_key.boolean_bitand(
+            &self.integer_key.scalar_ge_parallelized(&c.0, low),
+            &self.integer_key.scalar_le_parallelized(&c.0, high),
+        )
+    }
+
+    /// Checks if `byte` encrypts a non null ASCII character, i.e. a value in `1..=127`.
+    fn is_non_null_ascii(&self, byte: &FheAsciiChar) -> BooleanBlock {
+        self.is_in_char_range(byte, 1, 127)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{gen_keys_test, FheStrLength};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let s = CLIENT_KEY.encrypt_str_random_padding("a~Z", 2).unwrap();
+        let hex = SERVER_KEY.to_hex(&s);
+        assert_eq!(CLIENT_KEY.decrypt_string(&hex).unwrap(), "617e5a");
+        match hex.len() {
+            FheStrLength::Encrypted(length) => assert_eq!(CLIENT_KEY.decrypt_integer(length), 6),
+            FheStrLength::Clear(_) => panic!("The length should be encrypted"),
+        }
+
+        let (valid, decoded) = SERVER_KEY.from_hex(&hex);
+        assert!(CLIENT_KEY.decrypt_bool(&valid));
+        assert_eq!(CLIENT_KEY.decrypt_string(&decoded).unwrap(), "a~Z");
+    }
+
+    #[test]
+    fn test_from_hex_invalid() {
+        for invalid in ["6", "6g", "00", "ff"] {
+            let s = CLIENT_KEY.encrypt_str_padding(invalid, 1).unwrap();
+            let (valid, _) = SERVER_KEY.from_hex(&s);
+            assert!(!CLIENT_KEY.decrypt_bool(&valid), "{invalid}");
+        }
+        let s = CLIENT_KEY.encrypt_str("4A4b").unwrap();
+        let (valid, decoded) = SERVER_KEY.from_hex(&s);
+        assert!(CLIENT_KEY.decrypt_bool(&valid));
+        assert_eq!(CLIENT_KEY.decrypt_string(&decoded).unwrap(), "JK");
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        let cases = [
+            ("", ""),
+            ("a", "YQ=="),
+            ("ab", "YWI="),
+            ("abc", "YWJj"),
+            ("Hey?~", "SGV5P34="),
+        ];
+        for (clear, encoded) in cases {
+            let s = CLIENT_KEY.encrypt_str_random_padding(clear, 1).unwrap();
+            let result = SERVER_KEY.base64_encode(&s);
+            assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), encoded);
+
+            let (valid, decoded) = SERVER_KEY.base64_decode(&result);
+            assert!(CLIENT_KEY.decrypt_bool(&valid));
+            assert_eq!(CLIENT_KEY.decrypt_string(&decoded).unwrap(), clear);
+        }
+    }
+
+    #[test]
+    fn test_base64_decode_invalid() {
+        for invalid in ["YQ", "Y===", "YQ==YQ==", "Y!==", "/w=="] {
+            let s = CLIENT_KEY.encrypt_str(invalid).unwrap();
+            let (valid, _) = SERVER_KEY.base64_decode(&s);
+            assert!(!CLIENT_KEY.decrypt_bool(&valid), "{invalid}");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ciphertext::{ClearOrEncrypted, FheStrLength, FheString, Padding};
-use crate::pattern::{FheCharPattern, FhePattern};
+use crate::integer_arg::FheIntegerArg;
+use crate::pattern::{FheCharPattern, FhePattern, FheSlicePattern};
 use crate::server_key::StringServerKey;
 use tfhe::integer::{BooleanBlock, RadixCiphertext};
 
@@ -48,25 +49,19 @@ impl StringServerKey {
     /// first starts at index n, None otherwise. The pattern being a character clear or encrypted.
     /// Works even if s has padding anywhere.
     pub fn find_char(&self, s: &FheString, char_pattern: &impl FheCharPattern) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         if let FheStrLength::Clear(0) = s.length {
-            return (fhe_false, zero);
-        }
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match: BooleanBlock = char_pattern.fhe_eq(self, &s.content[n]);
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            // As s might has padding anywhere, the index is incremented if and only if the pattern
-            // is not found yet and the current character is not a padding zero.
-            // TODO: another version of the function when we know that the string has at worst final
-            // padding.
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+            return (self.create_false(), self.create_zero());
+        }
+        // As s might has padding anywhere, the index is incremented if and only if the pattern is
+        // not found yet and the current character is not a padding zero.
+        // TODO: another version of the function when we know that the string has at worst final
+        // padding.
+        let matches: Vec<BooleanBlock> = s
+            .content
+            .iter()
+            .map(|c| char_pattern.fhe_eq(self, c))
+            .collect();
+        self.first_match(s, &matches)
     }
 
     /// Same as find_char but from the end
@@ -170,59 +165,31 @@ impl StringServerKey {
         s: &FheString,
         pattern: &FheString,
     ) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = self.starts_with_encrypted_vec(&s.content[n..], pattern);
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec(&s.content[n..], pattern))
+            .collect();
+        self.first_match(s, &matches)
     }
 
     /// Search for pattern (a clear string) in s. Returns an FheOptionInt Some(n) if pattern is find
     /// and first starts at index n, None otherwise. It assumes that s is connected (its padding is
     /// at worst Initial and Final).
     pub fn connected_find_clear_string(&self, s: &FheString, pattern: &str) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = pattern.is_prefix_of_slice(self, &s.content[n..]);
-            //            let current_match = self.starts_with_encrypted_vec(&s.content[n..],
-            // pattern);
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| pattern.is_prefix_of_slice(self, &s.content[n..]))
+            .collect();
+        self.first_match(s, &matches)
     }
 
     /// same as connected_find_clear_string but from the end.
     pub fn connected_rfind_clear_string(&self, s: &FheString, pattern: &str) -> FheOptionInt {
-        let mut index = self.initial_index_rfind(&s.length);
         if pattern.is_empty() {
-            return (self.create_true(), index);
-        }
-        let mut found = self.create_false();
-        for n in (0..s.content.len()).rev() {
-            let increment_index = self.rincrement_index(s, n, &found);
-            //let current_match = self.starts_with_vec_clear(&s.content[n..],  pattern);
-            let current_match = pattern.is_prefix_of_slice(self, &s.content[n..]);
-
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-
-            self.integer_key
-                .sub_assign_parallelized(&mut index, &increment_index);
+            return (self.create_true(), self.initial_index_rfind(&s.length));
         }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| pattern.is_prefix_of_slice(self, &s.content[n..]))
+            .collect();
+        self.last_match(s, &matches)
     }
 
     /// Searches for pattern (an encrypted string) in s starting at (encrypted) index from. Assuming
@@ -234,26 +201,20 @@ impl StringServerKey {
         pattern: &FheString,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         match (s.content.len(), pattern.content.len()) {
-            (0, 0) => return (self.create_true(), zero),
-            (0, _) => return (self.eq_clear_char(&pattern.content[0], 0), zero),
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (0, _) => {
+                return (
+                    self.eq_clear_char(&pattern.content[0], 0),
+                    self.create_zero(),
+                )
+            }
             _ => (),
         }
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = self.integer_key.boolean_bitand(
-                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_le_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec(&s.content[n..], pattern))
+            .collect();
+        self.first_match_from(s, &matches, from)
     }
 
     /// Searches for pattern (a clear string) in s starting at (encrypted) index from. Assuming that
@@ -264,27 +225,15 @@ impl StringServerKey {
         pattern: &str,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         match (s.content.len(), pattern.len()) {
             (0, 0) => return (self.create_true(), from.clone()),
-            (0, _) => return (fhe_false, zero),
+            (0, _) => return (self.create_false(), self.create_zero()),
             _ => (),
         }
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = self.integer_key.boolean_bitand(
-                &pattern.is_prefix_of_slice(self, &s.content[n..]),
-                //                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_le_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| pattern.is_prefix_of_slice(self, &s.content[n..]))
+            .collect();
+        self.first_match_from(s, &matches, from)
     }
 
     /// Searches for pattern (a clear or encrypted character) in s starting at (encrypted) index
@@ -295,25 +244,13 @@ impl StringServerKey {
         pattern: &impl FheCharPattern,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         if s.content.is_empty() {
-            return (fhe_false, zero);
-        }
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = self.integer_key.boolean_bitand(
-                &pattern.is_prefix_of_slice(self, &s.content[n..]),
-                //                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_le_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
+            return (self.create_false(), self.create_zero());
         }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| pattern.is_prefix_of_slice(self, &s.content[n..]))
+            .collect();
+        self.first_match_from(s, &matches, from)
     }
 
     /// Same as find_from_final_padding but also works when pattern encrypts the empty string
@@ -324,26 +261,21 @@ impl StringServerKey {
         pattern: &FheString,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         match (s.content.len(), pattern.content.len()) {
-            (0, 0) => return (self.create_true(), zero),
-            (0, _) => return (self.eq_clear_char(&pattern.content[0], 0), zero),
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (0, _) => {
+                return (
+                    self.eq_clear_char(&pattern.content[0], 0),
+                    self.create_zero(),
+                )
+            }
             _ => (),
         }
-        let (mut found, mut index): (BooleanBlock, RadixCiphertext) = (fhe_false, zero);
-        for n in 0..s.content.len() {
-            let current_match = self.integer_key.boolean_bitand(
-                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_le_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            let increment_index = self.increment_index(s, n, &found);
-            self.integer_key
-                .add_assign_parallelized(&mut index, &increment_index);
-        }
-        index = self.integer_key.cmux_parallelized(
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec(&s.content[n..], pattern))
+            .collect();
+        let (found, index) = self.first_match_from(s, &matches, from);
+        let index = self.integer_key.cmux_parallelized(
             &self.is_empty_encrypted(pattern),
             &self.min_length_radix(&s.length, from),
             &index,
@@ -381,22 +313,13 @@ impl StringServerKey {
         s: &FheString,
         pattern: &FheString,
     ) -> FheOptionInt {
-        let fhe_false: BooleanBlock = self.create_false();
-        let initial_index = self.initial_index_rfind(&s.length);
-        let mut index = initial_index.clone();
-        let mut found = fhe_false;
-        for n in (0..s.content.len()).rev() {
-            let increment_index = self.rincrement_index(s, n, &found);
-            let current_match = self.starts_with_encrypted_vec(&s.content[n..], pattern);
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-
-            self.integer_key
-                .sub_assign_parallelized(&mut index, &increment_index);
-        }
-        index = self.integer_key.cmux_parallelized(
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec(&s.content[n..], pattern))
+            .collect();
+        let (found, index) = self.last_match(s, &matches);
+        let index = self.integer_key.cmux_parallelized(
             &self.is_empty_encrypted(pattern),
-            &initial_index,
+            &self.initial_index_rfind(&s.length),
             &index,
         );
         (found, index)
@@ -440,7 +363,6 @@ impl StringServerKey {
     ) -> FheOptionInt {
         let from_greater_than_zero = self.integer_key.scalar_gt_parallelized(from, 0);
         let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         match (s.content.len(), pattern.content.len()) {
             (0, 0) => return (from_greater_than_zero, zero),
             (0, _) => {
@@ -455,20 +377,11 @@ impl StringServerKey {
             _ => (),
         }
 
-        let mut index = self.initial_index_rfind(&s.length);
-        let mut found = fhe_false;
-        for n in (0..s.content.len()).rev() {
-            let increment_index = self.rincrement_index(s, n, &found);
-            let current_match = self.integer_key.boolean_bitand(
-                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_gt_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            self.integer_key
-                .sub_assign_parallelized(&mut index, &increment_index);
-        }
-        index = self.integer_key.cmux_parallelized(
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec(&s.content[n..], pattern))
+            .collect();
+        let (found, index) = self.last_match_before(s, &matches, from);
+        let index = self.integer_key.cmux_parallelized(
             &self.is_empty_encrypted(pattern),
             &self.min_length_radix(
                 &s.length,
@@ -486,28 +399,13 @@ impl StringServerKey {
         pattern: &str,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        //        let from_greater_than_zero = self.integer_key.scalar_gt_parallelized(from, 0);
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         if s.content.len() < pattern.len() {
-            return (fhe_false, zero);
-        }
-
-        let mut index = self.initial_index_rfind(&s.length);
-        let mut found = fhe_false;
-        for n in (0..s.content.len()).rev() {
-            let increment_index = self.rincrement_index(s, n, &found);
-            let current_match = self.integer_key.boolean_bitand(
-                &pattern.is_prefix_of_slice(self, &s.content[n..]),
-                //                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_gt_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            self.integer_key
-                .sub_assign_parallelized(&mut index, &increment_index);
+            return (self.create_false(), self.create_zero());
         }
-        (found, index)
+        let matches: Vec<BooleanBlock> = (0..s.content.len())
+            .map(|n| pattern.is_prefix_of_slice(self, &s.content[n..]))
+            .collect();
+        self.last_match_before(s, &matches, from)
     }
 
     /// Same as rfind_from_final_padding but for character pattern.
@@ -517,28 +415,12 @@ impl StringServerKey {
         pattern: &impl FheCharPattern,
         from: &RadixCiphertext,
     ) -> FheOptionInt {
-        //        let from_greater_than_zero = self.integer_key.scalar_gt_parallelized(from, 0);
-        let zero: RadixCiphertext = self.create_zero();
-        let fhe_false: BooleanBlock = self.create_false();
         if s.content.is_empty() {
-            return (fhe_false, zero);
+            return (self.create_false(), self.create_zero());
         }
-
-        let mut index = self.initial_index_rfind(&s.length);
-        let mut found = fhe_false;
-        for n in (0..s.content.len()).rev() {
-            let increment_index = self.rincrement_index(s, n, &found);
-            let current_match = self.integer_key.boolean_bitand(
-                &pattern.fhe_eq(self, &s.content[n]),
-                //                &self.starts_with_encrypted_vec(&s.content[n..], pattern),
-                &self.integer_key.scalar_gt_parallelized(from, n as u64),
-            );
-            self.integer_key
-                .boolean_bitor_assign(&mut found, &current_match);
-            self.integer_key
-                .sub_assign_parallelized(&mut index, &increment_index);
-        }
-        (found, index)
+        let matches: Vec<BooleanBlock> =
+            s.content.iter().map(|c| pattern.fhe_eq(self, c)).collect();
+        self.last_match_before(s, &matches, from)
     }
 
     /// Return a radix encoding the min of length and to.
@@ -603,14 +485,10 @@ impl StringServerKey {
                     .integer_key
                     .scalar_eq_parallelized(&self.bool_to_radix(found), 0),
             ),
-            _ => self.bool_to_radix(
-                &self.integer_key.boolean_bitand(
-                    &self.integer_key.boolean_bitnot(found),
-                    &self
-                        .integer_key
-                        .scalar_ne_parallelized(&s.content[content_index].0, 0),
-                ),
-            ),
+            _ => self.bool_to_radix(&self.integer_key.boolean_bitand(
+                &self.integer_key.boolean_bitnot(found),
+                &self.ne_clear_char(&s.content[content_index], 0),
+            )),
         }
     }
 
@@ -620,20 +498,314 @@ impl StringServerKey {
         content_index: usize,
         found: &BooleanBlock,
     ) -> RadixCiphertext {
-        self.bool_to_radix(
-            &self.integer_key.boolean_bitand(
-                &self.integer_key.boolean_bitnot(found),
-                &self
-                    .integer_key
-                    .scalar_ne_parallelized(&s.content[content_index].0, 0),
+        self.bool_to_radix(&self.integer_key.boolean_bitand(
+            &self.integer_key.boolean_bitnot(found),
+            &self.ne_clear_char(&s.content[content_index], 0),
+        ))
+    }
+
+    /// Returns an FheOptionInt Some(n) for the first index n of s at which `matches` encrypts
+    /// true, None if there is none. `matches` holds one encrypted boolean per character of s,
+    /// which is assumed to be connected (its padding is at worst initial and final).
+    pub fn first_match(&self, s: &FheString, matches: &[BooleanBlock]) -> FheOptionInt {
+        self.connected_first_match(s, matches, 0, None, None)
+    }
+
+    /// Same as `first_match` but only the indices greater or equal to (encrypted) from are
+    /// considered.
+    pub fn first_match_from(
+        &self,
+        s: &FheString,
+        matches: &[BooleanBlock],
+        from: &RadixCiphertext,
+    ) -> FheOptionInt {
+        self.connected_first_match(s, matches, 0, Some(from), None)
+    }
+
+    /// Same as `first_match` but returns the last index at which `matches` encrypts true.
+    pub fn last_match(&self, s: &FheString, matches: &[BooleanBlock]) -> FheOptionInt {
+        self.connected_last_match(s, matches, None, None)
+    }
+
+    /// Same as `last_match` but only the indices lower than (encrypted) to are considered.
+    /// Assuming that s has at worst final padding.
+    pub fn last_match_before(
+        &self,
+        s: &FheString,
+        matches: &[BooleanBlock],
+        to: &RadixCiphertext,
+    ) -> FheOptionInt {
+        self.connected_last_match(s, matches, Some(to), None)
+    }
+
+    /// Searches for pattern in s starting at index `start`, clear or encrypted. Returns an
+    /// FheOptionInt Some(n) if pattern is found and first starts at an index n >= start, None
+    /// otherwise. Like `s[start..].find(pattern)` but the index is relative to the start of s.
+    /// The positions of the content before a clear `start` are skipped.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a,b,c").unwrap();
+    /// let start = client_key.encrypt_integer(2u8);
+    /// let result = server_key.find_from(&encrypted_str, &',', &start);
+    /// let clear_result_bool = client_key.decrypt_integer(&server_key.bool_to_radix(&result.0));
+    /// assert_eq!(clear_result_bool, 1);
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 3);
+    /// ```
+    pub fn find_from(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        start: &impl FheIntegerArg,
+    ) -> FheOptionInt {
+        match s.padding {
+            Padding::Anywhere => {
+                self.connected_find_nth_from(&self.push_padding_to_end(s), pattern, None, start)
+            }
+            _ => self.connected_find_nth_from(s, pattern, None, start),
+        }
+    }
+
+    /// Searches for the n-th (counting from 0) non overlapping occurence of pattern in s, like
+    /// `s.match_indices(pattern).nth(n)`. Returns an FheOptionInt Some(i) if this occurence starts
+    /// at index i, None otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("aaaaa").unwrap();
+    /// let result = server_key.find_nth(&encrypted_str, &"aa", &1u32);
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 2);
+    /// ```
+    pub fn find_nth(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        n: &impl FheIntegerArg,
+    ) -> FheOptionInt {
+        let n = n.to_radix(self);
+        match s.padding {
+            Padding::Anywhere => self.connected_find_nth_from(
+                &self.push_padding_to_end(s),
+                pattern,
+                Some(&n),
+                &0usize,
             ),
+            _ => self.connected_find_nth_from(s, pattern, Some(&n), &0usize),
+        }
+    }
+
+    /// Searches, from the end, for the n-th (counting from 0) non overlapping occurence of pattern
+    /// in s, like `s.rmatch_indices(pattern).nth(n)`. Returns an FheOptionInt Some(i) if this
+    /// occurence starts at index i, None otherwise.
+    pub fn rfind_nth(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        n: &impl FheIntegerArg,
+    ) -> FheOptionInt {
+        let n = n.to_radix(self);
+        match s.padding {
+            Padding::Anywhere => {
+                self.connected_rfind_nth(&self.push_padding_to_end(s), pattern, &n)
+            }
+            _ => self.connected_rfind_nth(s, pattern, &n),
+        }
+    }
+
+    /// Implementation of `rfind_nth` for s connected (its padding is at worst initial and final).
+    fn connected_rfind_nth(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        n: &RadixCiphertext,
+    ) -> FheOptionInt {
+        let matches = pattern.prefix_matches_in(self, s, 0);
+        // Occurences of the empty pattern are one character apart.
+        let step = self
+            .integer_key
+            .scalar_max_parallelized(&pattern.length_radix(self), 1);
+        self.connected_last_match(s, &matches, None, Some((n, &step)))
+    }
+
+    /// Implementation of `find_from` and `find_nth` for s connected (its padding is at worst
+    /// initial and final). The positions of the content before a clear `start` are skipped.
+    fn connected_find_nth_from(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        n: Option<&RadixCiphertext>,
+        start: &impl FheIntegerArg,
+    ) -> FheOptionInt {
+        // An index is at most the content position, so the positions before start are skipped.
+        let skip = match start.clear_value() {
+            Some(clear_start) if clear_start > s.content.len() => {
+                return (self.create_false(), self.create_zero())
+            }
+            Some(clear_start) => clear_start,
+            None => 0,
+        };
+        let mut matches = pattern.prefix_matches_in(self, s, skip);
+        if s.padding == Padding::Final {
+            // The empty pattern matches in the final padding, positions after the end are masked.
+            let length = self.add_length_to_radix(&self.create_zero(), &s.length);
+            for (position, current_match) in (skip..).zip(&mut matches) {
+                self.integer_key.boolean_bitand_assign(
+                    current_match,
+                    &self
+                        .integer_key
+                        .scalar_ge_parallelized(&length, position as u64),
+                );
+            }
+        }
+        // Occurences of the empty pattern are one character apart.
+        let step = self
+            .integer_key
+            .scalar_max_parallelized(&pattern.length_radix(self), 1);
+        self.connected_first_match(
+            s,
+            &matches,
+            skip,
+            Some(&start.to_radix(self)),
+            n.map(|n| (n, &step)),
         )
     }
+
+    /// Searches for the first index of s connected at which `matches` encrypts true, considering
+    /// only the indices greater or equal to from if it is given. If `nth` is some (n, step), the
+    /// n-th (counting from 0) such index is returned instead, each index being at least step
+    /// after the previous one, like non overlapping occurences of a pattern of length step.
+    /// `matches` holds one encrypted boolean per position of the content from `skip` on, and
+    /// possibly one for the end of the content.
+    fn connected_first_match(
+        &self,
+        s: &FheString,
+        matches: &[BooleanBlock],
+        skip: usize,
+        from: Option<&RadixCiphertext>,
+        nth: Option<(&RadixCiphertext, &RadixCiphertext)>,
+    ) -> FheOptionInt {
+        let mut index = match s.padding {
+            Padding::None | Padding::Final => self.create_n(skip as u8),
+            _ => s.content[..skip]
+                .iter()
+                .fold(self.create_zero(), |index, c| {
+                    self.integer_key
+                        .add_parallelized(&index, &self.bool_to_radix(&self.ne_clear_char(c, 0)))
+                }),
+        };
+        let mut found = self.create_false();
+        let mut count = self.create_zero();
+        // The first index where the next occurence can start.
+        let mut limit = from.cloned().or_else(|| nth.map(|_| self.create_zero()));
+        for (position, current_match) in (skip..).zip(matches) {
+            // Until the occurence is found, index is the index in s of the current position.
+            let current_match = match &limit {
+                Some(limit) => self.integer_key.boolean_bitand(
+                    current_match,
+                    &self.integer_key.le_parallelized(limit, &index),
+                ),
+                None => current_match.clone(),
+            };
+            match nth {
+                Some((n, step)) => {
+                    self.integer_key.boolean_bitor_assign(
+                        &mut found,
+                        &self.integer_key.boolean_bitand(
+                            &current_match,
+                            &self.integer_key.eq_parallelized(&count, n),
+                        ),
+                    );
+                    self.integer_key
+                        .add_assign_parallelized(&mut count, &self.bool_to_radix(&current_match));
+                    limit = limit.map(|limit| {
+                        self.integer_key.cmux_parallelized(
+                            &current_match,
+                            &self.integer_key.add_parallelized(&index, step),
+                            &limit,
+                        )
+                    });
+                }
+                None => self
+                    .integer_key
+                    .boolean_bitor_assign(&mut found, &current_match),
+            }
+            if position < s.content.len() {
+                let increment_index = self.increment_index(s, position, &found);
+                self.integer_key
+                    .add_assign_parallelized(&mut index, &increment_index);
+            }
+        }
+        (found, index)
+    }
+
+    /// Same as `connected_first_match` but from the end, with `matches` starting at the first
+    /// position of the content. Only the indices lower than to are considered if it is given.
+    fn connected_last_match(
+        &self,
+        s: &FheString,
+        matches: &[BooleanBlock],
+        to: Option<&RadixCiphertext>,
+        nth: Option<(&RadixCiphertext, &RadixCiphertext)>,
+    ) -> FheOptionInt {
+        let mut index = self.initial_index_rfind(&s.length);
+        let mut found = self.create_false();
+        let mut count = self.create_zero();
+        // The start of the previous occurence, which the next one must end before. Initially one
+        // past the end, so that the empty pattern is found at the end.
+        let mut limit = self.integer_key.scalar_add_parallelized(&index, 1);
+        for (position, current_match) in matches.iter().enumerate().rev() {
+            if position < s.content.len() {
+                let increment_index = self.rincrement_index(s, position, &found);
+                self.integer_key
+                    .sub_assign_parallelized(&mut index, &increment_index);
+            }
+            let mut current_match = current_match.clone();
+            if let Some(to) = to {
+                self.integer_key.boolean_bitand_assign(
+                    &mut current_match,
+                    &self.integer_key.scalar_gt_parallelized(to, position as u64),
+                );
+            }
+            match nth {
+                Some((n, step)) => {
+                    // Until the occurence is found, index is the index in s of the current
+                    // position.
+                    self.integer_key.boolean_bitand_assign(
+                        &mut current_match,
+                        &self.integer_key.le_parallelized(
+                            &self.integer_key.add_parallelized(&index, step),
+                            &limit,
+                        ),
+                    );
+                    self.integer_key.boolean_bitor_assign(
+                        &mut found,
+                        &self.integer_key.boolean_bitand(
+                            &current_match,
+                            &self.integer_key.eq_parallelized(&count, n),
+                        ),
+                    );
+                    self.integer_key
+                        .add_assign_parallelized(&mut count, &self.bool_to_radix(&current_match));
+                    limit = self
+                        .integer_key
+                        .cmux_parallelized(&current_match, &index, &limit);
+                }
+                None => self
+                    .integer_key
+                    .boolean_bitor_assign(&mut found, &current_match),
+            }
+        }
+        (found, index)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ciphertext::gen_keys_test;
+    use crate::ciphertext::{gen_keys_test, FheStrLength, Padding};
     use crate::client_key::StringClientKey;
     use crate::server_key::StringServerKey;
     use crate::{compare_result, test_option_index_char_pattern, test_option_index_string_pattern};
@@ -712,4 +884,85 @@ mod tests {
     test_option_index_string_pattern!(rfind, "aaaa", "aaa");
     test_option_index_string_pattern!(rfind, "aaa", "aaa");
     test_option_index_string_pattern!(rfind, "aaa", "aaaa");
+
+    fn decrypt_option_index(result: &super::FheOptionInt) -> Option<usize> {
+        let found = CLIENT_KEY.decrypt_bool(&result.0);
+        found.then(|| CLIENT_KEY.decrypt_integer(&result.1) as usize)
+    }
+
+    #[test]
+    fn test_find_from() {
+        for (s, pattern, start) in [("a,b,c", ",", 2), ("a,b,c", ",", 4), ("abab", "", 3)] {
+            let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, 2).unwrap();
+            let encrypted_pattern = CLIENT_KEY.encrypt_str_random_padding(pattern, 1).unwrap();
+            let encrypted_start = CLIENT_KEY.encrypt_integer(start as u8);
+            let expected = s[start..].find(pattern).map(|i| i + start);
+            let result = SERVER_KEY.find_from(&encrypted_s, &pattern, &encrypted_start);
+            assert_eq!(decrypt_option_index(&result), expected);
+            let result = SERVER_KEY.find_from(&encrypted_s, &encrypted_pattern, &start);
+            assert_eq!(decrypt_option_index(&result), expected);
+        }
+        let encrypted_s = CLIENT_KEY.encrypt_str("a,b,c").unwrap();
+        let result = SERVER_KEY.find_from(&encrypted_s, &',', &2u32);
+        assert_eq!(decrypt_option_index(&result), Some(3));
+    }
+
+    #[test]
+    fn test_find_nth() {
+        for (s, pattern, n) in [
+            ("aaaaa", "aa", 1),
+            ("aaaaa", "aa", 2),
+            ("abcabc", "bc", 0),
+            ("ab", "", 2),
+            ("ab", "", 3),
+        ] {
+            let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, 1).unwrap();
+            let encrypted_pattern = CLIENT_KEY.encrypt_str_random_padding(pattern, 1).unwrap();
+            let encrypted_n = CLIENT_KEY.encrypt_integer(n as u8);
+            let expected = s.match_indices(pattern).nth(n).map(|(i, _)| i);
+            let result = SERVER_KEY.find_nth(&encrypted_s, &pattern, &encrypted_n);
+            assert_eq!(decrypt_option_index(&result), expected);
+            let result = SERVER_KEY.find_nth(&encrypted_s, &encrypted_pattern, &n);
+            assert_eq!(decrypt_option_index(&result), expected);
+
+            let expected = s.rmatch_indices(pattern).nth(n).map(|(i, _)| i);
+            let result = SERVER_KEY.rfind_nth(&encrypted_s, &pattern, &encrypted_n);
+            assert_eq!(decrypt_option_index(&result), expected);
+            let result = SERVER_KEY.rfind_nth(&encrypted_s, &encrypted_pattern, &n);
+            assert_eq!(decrypt_option_index(&result), expected);
+        }
+        let encrypted_s = CLIENT_KEY.encrypt_str("abcb").unwrap();
+        let result = SERVER_KEY.rfind_nth(&encrypted_s, &'b', &1u32);
+        assert_eq!(decrypt_option_index(&result), Some(1));
+    }
+
+    #[test]
+    fn test_find_nth_initial_padding() {
+        // "abab" with initial and final padding, which is searched without pushing the padding.
+        let encrypted_s = CLIENT_KEY
+            .encrypt_ascii_vec(
+                &[0, 0, b'a', b'b', b'a', b'b', 0],
+                Padding::InitialAndFinal,
+                FheStrLength::Clear(4),
+            )
+            .unwrap();
+        for (pattern, start) in [("b", 2), ("", 1), ("", 4), ("a", 3), ("", 5)] {
+            let expected = "abab"
+                .get(start..)
+                .and_then(|tail| tail.find(pattern).map(|i| i + start));
+            let result = SERVER_KEY.find_from(&encrypted_s, &pattern, &start);
+            assert_eq!(decrypt_option_index(&result), expected);
+            let encrypted_start = CLIENT_KEY.encrypt_integer(start as u8);
+            let result = SERVER_KEY.find_from(&encrypted_s, &pattern, &encrypted_start);
+            assert_eq!(decrypt_option_index(&result), expected);
+        }
+        for (pattern, n) in [("ab", 1), ("", 0), ("", 4), ("", 5)] {
+            let expected = "abab".match_indices(pattern).nth(n).map(|(i, _)| i);
+            let result = SERVER_KEY.find_nth(&encrypted_s, &pattern, &n);
+            assert_eq!(decrypt_option_index(&result), expected);
+            let expected = "abab".rmatch_indices(pattern).nth(n).map(|(i, _)| i);
+            let result = SERVER_KEY.rfind_nth(&encrypted_s, &pattern, &n);
+            assert_eq!(decrypt_option_index(&result), expected);
+        }
+    }
 }
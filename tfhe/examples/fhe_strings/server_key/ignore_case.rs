@@ -0,0 +1,551 @@
+use crate::ciphertext::{ClearOrEncrypted, FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::pattern::FhePattern;
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::split::FheSplit;
+use crate::server_key::strip::FheOptionString;
+use crate::server_key::StringServerKey;
+use tfhe::integer::BooleanBlock;
+
+impl StringServerKey {
+    /// Same as `find` but ignoring ASCII case.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("An Error").unwrap();
+    /// let result = server_key.find_ignore_case(&encrypted_str, &"error");
+    /// let clear_result = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result, 3);
+    /// ```
+    pub fn find_ignore_case(
+        &self,
+        haystack: &FheString,
+        pattern: &impl FhePattern,
+    ) -> FheOptionInt {
+        pattern.find_ignore_case_in(self, haystack)
+    }
+
+    /// Same as `rfind` but ignoring ASCII case.
+    pub fn rfind_ignore_case(
+        &self,
+        haystack: &FheString,
+        pattern: &impl FhePattern,
+    ) -> FheOptionInt {
+        pattern.rfind_ignore_case_in(self, haystack)
+    }
+
+    /// Same as `contains` but ignoring ASCII case.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("An Error").unwrap();
+    /// let result = server_key.contains_ignore_case(&encrypted_str, &"ERR");
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn contains_ignore_case(&self, s: &FheString, pattern: &impl FhePattern) -> BooleanBlock {
+        pattern.is_contained_ignore_case_in(self, s)
+    }
+
+    /// Same as `starts_with` but ignoring ASCII case.
+    pub fn starts_with_ignore_case(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+    ) -> BooleanBlock {
+        pattern.is_prefix_ignore_case_of_string(self, s)
+    }
+
+    /// Same as `ends_with` but ignoring ASCII case.
+    pub fn ends_with_ignore_case(&self, s: &FheString, pattern: &impl FhePattern) -> BooleanBlock {
+        pattern.is_suffix_ignore_case_of_string(self, s)
+    }
+
+    /// Same as `split` but ignoring ASCII case when searching for pattern. The parts keep the case
+    /// of `s`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("aXbxc").unwrap();
+    /// let fhe_result = server_key.split_ignore_case(&encrypted_str, &'x');
+    /// let clear_len = client_key.decrypt_integer(&fhe_result.number_parts);
+    /// let clear_split: Vec<String> = fhe_result.parts[..(clear_len as usize)]
+    ///     .iter()
+    ///     .map(|s| client_key.decrypt_string(s).unwrap())
+    ///     .collect();
+    /// assert_eq!(clear_split, vec!["a", "b", "c"]);
+    /// ```
+    pub fn split_ignore_case(&self, s: &FheString, pattern: &impl FhePattern) -> FheSplit {
+        pattern.split_ignore_case_string(self, s)
+    }
+
+    /// Same as `replace` but ignoring ASCII case when searching for old_pattern.
+    pub fn replace_ignore_case(
+        &self,
+        s: &FheString,
+        old_pattern: &impl FhePattern,
+        new: &impl FhePattern,
+    ) -> FheString {
+        new.insert_in(self, &self.split_ignore_case(s, old_pattern))
+    }
+
+    /// Same as `strip_prefix` but ignoring ASCII case. The result keeps the case of `s`.
+    pub fn strip_prefix_ignore_case(
+        &self,
+        s: &FheString,
+        prefix: &impl FhePattern,
+    ) -> FheOptionString {
+        prefix.strip_prefix_ignore_case_in(self, s)
+    }
+
+    pub fn find_clear_string_ignore_case(&self, s: &FheString, pattern: &str) -> FheOptionInt {
+        match (s.content.len(), pattern.len()) {
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (content_length, pattern_length) if pattern_length > content_length => {
+                return (self.create_false(), self.create_zero())
+            }
+            _ => (),
+        }
+        let connected_s = match s.padding {
+            Padding::Anywhere => self.push_padding_to_end(s),
+            _ => s.clone(),
+        };
+        self.first_match(
+            &connected_s,
+            &self.clear_matches_ignore_case(&connected_s, pattern),
+        )
+    }
+
+    pub fn rfind_clear_string_ignore_case(&self, s: &FheString, pattern: &str) -> FheOptionInt {
+        match (s.content.len(), pattern.len()) {
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (content_length, pattern_length) if pattern_length > content_length => {
+                return (self.create_false(), self.create_zero())
+            }
+            (_, 0) => return (self.create_true(), self.initial_index_rfind(&s.length)),
+            _ => (),
+        }
+        let connected_s = match s.padding {
+            Padding::Anywhere => self.push_padding_to_end(s),
+            _ => s.clone(),
+        };
+        self.last_match(
+            &connected_s,
+            &self.clear_matches_ignore_case(&connected_s, pattern),
+        )
+    }
+
+    pub fn contains_clear_string_ignore_case(&self, s: &FheString, pattern: &str) -> BooleanBlock {
+        match (s.content.len(), pattern.len()) {
+            (0, 0) => return self.create_true(),
+            (content_length, pattern_length) if pattern_length > content_length => {
+                return self.create_false()
+            }
+            _ => (),
+        }
+        let connected_s = match s.padding {
+            Padding::Anywhere => self.push_padding_to_end(s),
+            _ => s.clone(),
+        };
+        self.any_match(&self.clear_matches_ignore_case(&connected_s, pattern))
+    }
+
+    pub fn starts_with_clear_string_ignore_case(
+        &self,
+        s: &FheString,
+        pattern: &str,
+    ) -> BooleanBlock {
+        match s.len() {
+            FheStrLength::Clear(clear_length) if *clear_length < pattern.len() => {
+                return self.create_false()
+            }
+            _ if s.content.len() < pattern.len() => return self.create_false(),
+            _ => (),
+        }
+        match s.padding {
+            Padding::None | Padding::Final => {
+                self.starts_with_vec_clear_ignore_case(&s.content, pattern)
+            }
+            _ => self
+                .starts_with_vec_clear_ignore_case(&self.push_padding_to_end(s).content, pattern),
+        }
+    }
+
+    pub fn ends_with_clear_string_ignore_case(&self, s: &FheString, pattern: &str) -> BooleanBlock {
+        let reversed_pattern = pattern.chars().rev().collect::<String>();
+        self.starts_with_clear_string_ignore_case(
+            &self.reverse_string_content(s),
+            &reversed_pattern,
+        )
+    }
+
+    pub fn split_clear_ignore_case(&self, s: &FheString, pattern: &str) -> FheSplit {
+        if pattern.is_empty() {
+            // Splitting at the empty pattern does not depend on the case.
+            return self.split_clear(s, pattern);
+        }
+        let unpadded_s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        let matches = self.clear_matches_ignore_case(&unpadded_s, pattern);
+        self.split_final_padding_with_find(
+            &unpadded_s,
+            &FheStrLength::Clear(pattern.len()),
+            |from| self.first_match_from(&unpadded_s, &matches, from),
+        )
+    }
+
+    pub fn strip_clear_prefix_ignore_case(&self, s: &FheString, prefix: &str) -> FheOptionString {
+        match s.len() {
+            FheStrLength::Clear(clear_length) if *clear_length < prefix.len() => {
+                return (self.create_false(), s.clone())
+            }
+            _ if s.content.len() < prefix.len() => return (self.create_false(), s.clone()),
+            _ => (),
+        }
+        let unpadded_s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        let is_prefix = self.starts_with_clear_string_ignore_case(&unpadded_s, prefix);
+        let start = self.integer_key.cmux_parallelized(
+            &is_prefix,
+            &self.create_n(prefix.len() as u8),
+            &self.create_zero(),
+        );
+        (
+            is_prefix,
+            self.substring_from_encrypted_final_padding(&unpadded_s, &start),
+        )
+    }
+
+    pub fn find_string_ignore_case(&self, s: &FheString, pattern: &FheString) -> FheOptionInt {
+        match (s.content.len(), pattern.content.len()) {
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (0, _) => {
+                return (
+                    self.eq_clear_char(&pattern.content[0], 0),
+                    self.create_zero(),
+                )
+            }
+            _ => (),
+        }
+        self.connected_pair_dispatch(s, pattern, |s, pattern| {
+            self.first_match(s, &self.encrypted_matches_ignore_case(s, pattern))
+        })
+    }
+
+    pub fn rfind_string_ignore_case(&self, s: &FheString, pattern: &FheString) -> FheOptionInt {
+        match (s.content.len(), pattern.content.len()) {
+            (0, 0) => return (self.create_true(), self.create_zero()),
+            (0, _) => {
+                return (
+                    self.eq_clear_char(&pattern.content[0], 0),
+                    self.create_zero(),
+                )
+            }
+            _ => (),
+        }
+        self.connected_pair_dispatch(s, pattern, |s, pattern| {
+            let (found, index) =
+                self.last_match(s, &self.encrypted_matches_ignore_case(s, pattern));
+            // The empty pattern is found at the end of s.
+            let index = self.integer_key.cmux_parallelized(
+                &self.is_empty_encrypted(pattern),
+                &self.initial_index_rfind(&s.length),
+                &index,
+            );
+            (found, index)
+        })
+    }
+
+    pub fn contains_string_ignore_case(&self, s: &FheString, pattern: &FheString) -> BooleanBlock {
+        match (s.content.len(), pattern.content.len()) {
+            (0, 0) => return self.create_true(),
+            (0, _) => return self.eq_clear_char(&pattern.content[0], 0),
+            _ => (),
+        }
+        self.connected_pair_dispatch(s, pattern, |s, pattern| {
+            self.any_match(&self.encrypted_matches_ignore_case(s, pattern))
+        })
+    }
+
+    pub fn starts_with_string_ignore_case(
+        &self,
+        s: &FheString,
+        pattern: &FheString,
+    ) -> BooleanBlock {
+        match (&pattern.length, &s.length) {
+            (&FheStrLength::Clear(l_pattern), &FheStrLength::Clear(l)) if l_pattern > l => {
+                return self.create_false()
+            }
+            (&FheStrLength::Clear(l_pattern), _) if l_pattern > s.content.len() => {
+                return self.create_false()
+            }
+            _ => (),
+        }
+        let unpadded_s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        let unpadded_pattern = match pattern.padding {
+            Padding::None | Padding::Final => pattern.clone(),
+            _ => self.push_padding_to_end(pattern),
+        };
+        self.starts_with_encrypted_vec_ignore_case(&unpadded_s.content, &unpadded_pattern)
+    }
+
+    pub fn ends_with_string_ignore_case(&self, s: &FheString, pattern: &FheString) -> BooleanBlock {
+        self.starts_with_string_ignore_case(
+            &self.reverse_string_content(s),
+            &self.reverse_string_content(pattern),
+        )
+    }
+
+    pub fn split_encrypted_ignore_case(&self, s: &FheString, pattern: &FheString) -> FheSplit {
+        match &pattern.length {
+            // Splitting at the empty pattern does not depend on the case.
+            ClearOrEncrypted::Clear(0) => self.split_encrypted(s, pattern),
+            ClearOrEncrypted::Clear(_) => self.padding_pair_dispatch(s, pattern, |s1, s2| {
+                let matches = self.encrypted_matches_ignore_case(s1, s2);
+                self.split_final_padding_with_find(s1, &s2.length, |from| {
+                    self.first_match_from(s1, &matches, from)
+                })
+            }),
+            _ => self.padding_pair_dispatch(s, pattern, |s1, s2| {
+                let matches = self.encrypted_matches_ignore_case(s1, s2);
+                let empty_pattern = self.is_empty_encrypted(s2);
+                self.split_encrypted_final_padding_allow_empty_pattern_with_find(s1, s2, |from| {
+                    let (found, index) = self.first_match_from(s1, &matches, from);
+                    // The empty pattern is found at `from`, or at the end of s1.
+                    let index = self.integer_key.cmux_parallelized(
+                        &empty_pattern,
+                        &self.min_length_radix(&s1.length, from),
+                        &index,
+                    );
+                    (found, index)
+                })
+            }),
+        }
+    }
+
+    pub fn strip_encrypted_prefix_ignore_case(
+        &self,
+        s: &FheString,
+        prefix: &FheString,
+    ) -> FheOptionString {
+        match (&s.length, &prefix.length) {
+            (&FheStrLength::Clear(l), &FheStrLength::Clear(l_prefix)) if l_prefix > l => {
+                return (self.create_false(), s.clone())
+            }
+            (_, &FheStrLength::Clear(l_prefix)) if l_prefix > s.content.len() => {
+                return (self.create_false(), s.clone())
+            }
+            _ => (),
+        }
+        let unpadded_s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        let is_prefix = self.starts_with_string_ignore_case(&unpadded_s, prefix);
+        let zero = self.create_zero();
+        let start = self.integer_key.cmux_parallelized(
+            &is_prefix,
+            &self.add_length_to_radix(&zero, &prefix.length),
+            &zero,
+        );
+        (
+            is_prefix,
+            self.substring_from_encrypted_final_padding(&unpadded_s, &start),
+        )
+    }
+
+    /// Same as `starts_with_vec_clear` but ignoring ASCII case. Returns an encryption of false if
+    /// prefix is longer than s.
+    pub fn starts_with_vec_clear_ignore_case(
+        &self,
+        s: &[FheAsciiChar],
+        prefix: &str,
+    ) -> BooleanBlock {
+        if prefix.len() > s.len() {
+            return self.create_false();
+        }
+        let mut result = self.create_true();
+        for (c, clear_char) in s.iter().zip(prefix.bytes()) {
+            self.integer_key
+                .boolean_bitand_assign(&mut result, &self.eq_clear_char_ignore_case(c, clear_char))
+        }
+        result
+    }
+
+    /// Same as `starts_with_encrypted_vec` but ignoring ASCII case. The prefix should have at
+    /// worst final padding.
+    pub fn starts_with_encrypted_vec_ignore_case(
+        &self,
+        s: &[FheAsciiChar],
+        prefix: &FheString,
+    ) -> BooleanBlock {
+        let mut result = self.create_true();
+        for (c, prefix_char) in s.iter().zip(&prefix.content) {
+            let char_match = self.eq_char_ignore_case(c, prefix_char);
+            self.integer_key.boolean_bitand_assign(
+                &mut result,
+                &match prefix.padding {
+                    Padding::None => char_match,
+                    _ => self
+                        .integer_key
+                        .boolean_bitor(&char_match, &self.eq_clear_char(prefix_char, 0)),
+                },
+            )
+        }
+        // The characters of prefix beyond the end of s should be padding zeros.
+        if prefix.content.len() > s.len() {
+            return self
+                .integer_key
+                .boolean_bitand(&result, &self.eq_clear_char(&prefix.content[s.len()], 0));
+        }
+        result
+    }
+
+    /// For each index n of s, an encryption of true if the clear pattern occurs in s at index n,
+    /// ignoring ASCII case.
+    fn clear_matches_ignore_case(&self, s: &FheString, pattern: &str) -> Vec<BooleanBlock> {
+        (0..s.content.len())
+            .map(|n| self.starts_with_vec_clear_ignore_case(&s.content[n..], pattern))
+            .collect()
+    }
+
+    /// For each index n of s, an encryption of true if pattern, which has at worst final padding,
+    /// occurs in s at index n, ignoring ASCII case.
+    fn encrypted_matches_ignore_case(
+        &self,
+        s: &FheString,
+        pattern: &FheString,
+    ) -> Vec<BooleanBlock> {
+        (0..s.content.len())
+            .map(|n| self.starts_with_encrypted_vec_ignore_case(&s.content[n..], pattern))
+            .collect()
+    }
+
+    fn any_match(&self, matches: &[BooleanBlock]) -> BooleanBlock {
+        matches.iter().fold(self.create_false(), |a, b| {
+            self.integer_key.boolean_bitor(&a, b)
+        })
+    }
+
+    /// Calls f with s, pushing back its padding zeros if they are anywhere, and with pattern,
+    /// pushing back its padding zeros if they are not final.
+    fn connected_pair_dispatch<T, F>(&self, s: &FheString, pattern: &FheString, f: F) -> T
+    where
+        F: Fn(&FheString, &FheString) -> T,
+    {
+        match (s.padding, pattern.padding) {
+            (Padding::Anywhere, Padding::None | Padding::Final) => {
+                f(&self.push_padding_to_end(s), pattern)
+            }
+            (Padding::Anywhere, _) => f(
+                &self.push_padding_to_end(s),
+                &self.push_padding_to_end(pattern),
+            ),
+            (_, Padding::None | Padding::Final) => f(s, pattern),
+            _ => f(s, &self.push_padding_to_end(pattern)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_find_ignore_case() {
+        let s = CLIENT_KEY.encrypt_str_random_padding("aBcAbc", 2).unwrap();
+        let encrypted_pattern = CLIENT_KEY.encrypt_str_padding("ABC", 1).unwrap();
+
+        let (found, index) = SERVER_KEY.find_ignore_case(&s, &"bC");
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 1);
+
+        let (found, index) = SERVER_KEY.rfind_ignore_case(&s, &encrypted_pattern);
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 3);
+
+        let (found, index) = SERVER_KEY.find_ignore_case(&s, &'C');
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 2);
+
+        let (found, _) = SERVER_KEY.find_ignore_case(&s, &"abd");
+        assert!(!CLIENT_KEY.decrypt_bool(&found));
+    }
+
+    #[test]
+    fn test_contains_starts_ends_with_ignore_case() {
+        let s = CLIENT_KEY.encrypt_str_padding("Hello", 1).unwrap();
+        let encrypted_char = CLIENT_KEY.encrypt_ascii_char(b'L');
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.contains_ignore_case(&s, &encrypted_char)));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_ignore_case(&s, &"hE")));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.ends_with_ignore_case(&s, &"LO")));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.ends_with_ignore_case(&s, &"he")));
+
+        let encrypted_pattern = CLIENT_KEY.encrypt_str_random_padding("eLl", 1).unwrap();
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.contains_ignore_case(&s, &encrypted_pattern)));
+        assert!(
+            !CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_ignore_case(&s, &encrypted_pattern))
+        );
+        let encrypted_suffix = CLIENT_KEY.encrypt_str_padding("LLo", 2).unwrap();
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.ends_with_ignore_case(&s, &encrypted_suffix)));
+    }
+
+    #[test]
+    fn test_split_ignore_case() {
+        let s = CLIENT_KEY.encrypt_str("aXYbxyC").unwrap();
+        let fhe_split = SERVER_KEY.split_ignore_case(&s, &"xy");
+        let number_parts = CLIENT_KEY.decrypt_integer(&fhe_split.number_parts) as usize;
+        let clear_split: Vec<String> = fhe_split.parts[..number_parts]
+            .iter()
+            .map(|s| CLIENT_KEY.decrypt_string(s).unwrap())
+            .collect();
+        assert_eq!(clear_split, vec!["a", "b", "C"]);
+
+        let encrypted_pattern = CLIENT_KEY.encrypt_str_padding("Xy", 1).unwrap();
+        let fhe_split = SERVER_KEY.split_ignore_case(&s, &encrypted_pattern);
+        let number_parts = CLIENT_KEY.decrypt_integer(&fhe_split.number_parts) as usize;
+        let clear_split: Vec<String> = fhe_split.parts[..number_parts]
+            .iter()
+            .map(|s| CLIENT_KEY.decrypt_string(s).unwrap())
+            .collect();
+        assert_eq!(clear_split, vec!["a", "b", "C"]);
+    }
+
+    #[test]
+    fn test_replace_ignore_case() {
+        let s = CLIENT_KEY.encrypt_str("aBab").unwrap();
+        let result = SERVER_KEY.replace_ignore_case(&s, &'b', &"c");
+        assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), "acac");
+    }
+
+    #[test]
+    fn test_strip_prefix_ignore_case() {
+        let s = CLIENT_KEY.encrypt_str_random_padding("ABcd", 1).unwrap();
+        let (is_prefix, result) = SERVER_KEY.strip_prefix_ignore_case(&s, &"ab");
+        assert!(CLIENT_KEY.decrypt_bool(&is_prefix));
+        assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), "cd");
+
+        let encrypted_prefix = CLIENT_KEY.encrypt_str_padding("aC", 1).unwrap();
+        let (is_prefix, result) = SERVER_KEY.strip_prefix_ignore_case(&s, &encrypted_prefix);
+        assert!(!CLIENT_KEY.decrypt_bool(&is_prefix));
+        assert_eq!(CLIENT_KEY.decrypt_string(&result).unwrap(), "ABcd");
+    }
+}
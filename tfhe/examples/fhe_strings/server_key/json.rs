@@ -0,0 +1,360 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::pattern::FheSlicePattern;
+use crate::server_key::strip::FheOptionString;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// Maximal nesting depth of objects and arrays in the documents of `json_get`, the depth being
+/// encrypted on two blocks.
+pub const JSON_MAX_DEPTH: usize = 15;
+
+const DEPTH_BLOCKS: usize = 2;
+
+/// Escape sequences of JSON strings that are replaced by another character. The other escaped
+/// characters (`"`, `\` and `/`) stand for themselves.
+const JSON_ESCAPES: [(u8, u8); 5] = [
+    (b'n', b'\n'),
+    (b't', b'\t'),
+    (b'r', b'\r'),
+    (b'b', 8),
+    (b'f', 12),
+];
+
+/// Encrypted state of the JSON tokenizer, updated character by character by `json_token`.
+struct JsonTokenizer {
+    in_string: BooleanBlock,
+    escaped: BooleanBlock,
+    /// Depth of nested objects and arrays.
+    depth: RadixCiphertext,
+    /// Depth of nested arrays.
+    array_depth: RadixCiphertext,
+    expect_key: BooleanBlock,
+}
+
+/// Role of a character of a JSON document, given the state of the tokenizer before it.
+struct JsonToken {
+    is_char: BooleanBlock,
+    /// Characters outside strings, where the JSON syntax is.
+    structural: BooleanBlock,
+    /// Quotes delimiting strings and backslashes starting escape sequences.
+    delimiter: BooleanBlock,
+    /// Characters following a backslash in a string.
+    escaped_char: BooleanBlock,
+    /// Opening quotes of keys outside arrays.
+    key_start: BooleanBlock,
+    colon: BooleanBlock,
+    /// Commas and closing brackets, ending the value at their depth.
+    value_end: BooleanBlock,
+    /// Depth before the character.
+    depth: RadixCiphertext,
+}
+
+impl StringServerKey {
+    /// Extracts the value of the member designated by `path` in the JSON document s. The path is a
+    /// list of keys separated by dots, each key being looked up in the object value of the
+    /// previous one. Returns an FheOptionString Some(value) if the member exists, None otherwise
+    /// and for an empty path. String values are returned without their quotes and with their
+    /// escape sequences replaced (except `\u` sequences), other values (numbers, booleans,
+    /// objects etc.) are returned as in s, without the whitespaces outside strings. If a key
+    /// occurs several times, the first value is returned. Keys inside arrays are never matched.
+    /// Objects and arrays should be nested at most `JSON_MAX_DEPTH` deep.
+    ///
+    /// The document is scanned once by an encrypted tokenizer, which marks the keys outside
+    /// arrays, the colons and the ends of values with their depth. Each key of the path is then
+    /// searched with `first_match_from`, the sweep behind `find`, among the keys at its depth
+    /// from the start of the value of the previous key, and the value is cut with
+    /// `substring_encrypted`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let document = r#"{"id": 7, "user": {"name": "bob", "email": "b@x.io"}}"#;
+    /// let encrypted_str = client_key.encrypt_str(document).unwrap();
+    /// let (found, value) = server_key.json_get(&encrypted_str, "user.email");
+    /// assert_eq!(client_key.decrypt_integer(&server_key.bool_to_radix(&found)), 1);
+    /// assert_eq!(client_key.decrypt_string(&value).unwrap(), "b@x.io");
+    /// ```
+    pub fn json_get(&self, s: &FheString, path: &str) -> FheOptionString {
+        let keys: Vec<&str> = path.split('.').collect();
+        // The key of index k is at depth k + 1.
+        if path.is_empty() || keys.len() > JSON_MAX_DEPTH {
+            return (
+                self.create_false(),
+                FheString {
+                    content: Vec::new(),
+                    padding: Padding::None,
+                    length: FheStrLength::Clear(0),
+                },
+            );
+        }
+        let s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        let mut tokenizer = self.new_json_tokenizer();
+        let tokens: Vec<JsonToken> = s
+            .content
+            .iter()
+            .map(|c| self.json_token(&mut tokenizer, c))
+            .collect();
+
+        let mut found = self.create_true();
+        let mut value_start = self.create_zero();
+        let mut value_end = self.add_length_to_radix(&self.create_zero(), &s.length);
+        for (level, key) in keys.iter().enumerate() {
+            let at_level: Vec<BooleanBlock> = tokens
+                .iter()
+                .map(|token| {
+                    self.integer_key
+                        .scalar_eq_parallelized(&token.depth, level as u64 + 1)
+                })
+                .collect();
+            let keep_at_level = |flags: Vec<&BooleanBlock>| -> Vec<BooleanBlock> {
+                flags
+                    .into_iter()
+                    .zip(&at_level)
+                    .map(|(flag, at_level)| self.integer_key.boolean_bitand(flag, at_level))
+                    .collect()
+            };
+
+            // Keys are compared with their quotes, so that a key is not matched by its prefixes.
+            let quoted_key = format!("\"{}\"", key);
+            let key_matches: Vec<BooleanBlock> = tokens
+                .iter()
+                .enumerate()
+                .map(|(i, token)| {
+                    self.integer_key.boolean_bitand(
+                        &token.key_start,
+                        &quoted_key
+                            .as_str()
+                            .is_prefix_of_slice(self, &s.content[i..]),
+                    )
+                })
+                .collect();
+            let key_matches = keep_at_level(key_matches.iter().collect());
+            let (key_found, key_index) = self.first_match_from(&s, &key_matches, &value_start);
+            self.integer_key
+                .boolean_bitand_assign(&mut found, &key_found);
+            // The key should be in the value of the previous key.
+            self.integer_key.boolean_bitand_assign(
+                &mut found,
+                &self.integer_key.lt_parallelized(&key_index, &value_end),
+            );
+
+            // The value starts after the colon following the key and ends at the next comma or
+            // closing bracket at its depth.
+            let colons = keep_at_level(tokens.iter().map(|token| &token.colon).collect());
+            let (_, colon_index) = self.first_match_from(&s, &colons, &key_index);
+            value_start = self.integer_key.scalar_add_parallelized(&colon_index, 1);
+            let value_ends = keep_at_level(tokens.iter().map(|token| &token.value_end).collect());
+            (_, value_end) = self.first_match_from(&s, &value_ends, &value_start);
+        }
+        let (_, value) = self.substring_encrypted(&s, &value_start, &value_end);
+        (found, self.normalize_json_value(&value))
+    }
+
+    /// Removes the whitespaces outside strings of the JSON value s, as well as its quotes and
+    /// escape sequences if it is a string.
+    fn normalize_json_value(&self, s: &FheString) -> FheString {
+        let zero = FheAsciiChar(self.create_zero());
+        let mut tokenizer = self.new_json_tokenizer();
+        let mut content = Vec::with_capacity(s.content.len());
+        let mut length = self.create_zero();
+        for c in &s.content {
+            let token = self.json_token(&mut tokenizer, c);
+            let at_top = self.integer_key.scalar_eq_parallelized(&token.depth, 0);
+            let dropped = self.integer_key.boolean_bitor(
+                &self
+                    .integer_key
+                    .boolean_bitand(&token.structural, &self.is_ascii_white_space(c)),
+                &self.integer_key.boolean_bitand(&token.delimiter, &at_top),
+            );
+            let kept = self
+                .integer_key
+                .boolean_bitand(&token.is_char, &self.integer_key.boolean_bitnot(&dropped));
+            let unescaped = self
+                .integer_key
+                .boolean_bitand(&token.escaped_char, &at_top);
+            let output_char = self.cmux_char(&unescaped, &self.unescape_json_char(c), c);
+            content.push(self.cmux_char(&kept, &output_char, &zero));
+            self.integer_key
+                .add_assign_parallelized(&mut length, &self.bool_to_radix(&kept));
+        }
+        FheString {
+            content,
+            padding: Padding::Anywhere,
+            length: FheStrLength::Encrypted(length),
+        }
+    }
+
+    /// Returns the state of the JSON tokenizer at the start of a document.
+    fn new_json_tokenizer(&self) -> JsonTokenizer {
+        JsonTokenizer {
+            in_string: self.create_false(),
+            escaped: self.create_false(),
+            depth: self.integer_key.create_trivial_zero_radix(DEPTH_BLOCKS),
+            array_depth: self.integer_key.create_trivial_zero_radix(DEPTH_BLOCKS),
+            expect_key: self.create_false(),
+        }
+    }
+
+    /// Returns the role of the character c in a JSON document given the state of the tokenizer
+    /// before c, and updates this state.
+    fn json_token(&self, tokenizer: &mut JsonTokenizer, c: &FheAsciiChar) -> JsonToken {
+        let is_char = self.ne_clear_char(c, 0);
+        let structural = self.integer_key.boolean_bitand(
+            &is_char,
+            &self.integer_key.boolean_bitnot(&tokenizer.in_string),
+        );
+        let is_quote = self.eq_clear_char(c, b'"');
+        let unescaped_in_string = self.integer_key.boolean_bitand(
+            &tokenizer.in_string,
+            &self.integer_key.boolean_bitnot(&tokenizer.escaped),
+        );
+        let opening_quote = self.integer_key.boolean_bitand(&structural, &is_quote);
+        let closing_quote = self
+            .integer_key
+            .boolean_bitand(&unescaped_in_string, &is_quote);
+        let escape_start = self
+            .integer_key
+            .boolean_bitand(&unescaped_in_string, &self.eq_clear_char(c, b'\\'));
+        let structural_is = |chars: &[u8]| {
+            self.integer_key
+                .boolean_bitand(&structural, &self.is_any_clear_char(c, chars))
+        };
+        let opening_array = structural_is(b"[");
+        let closing_array = structural_is(b"]");
+        let opening_bracket = self
+            .integer_key
+            .boolean_bitor(&structural_is(b"{"), &opening_array);
+        let closing_bracket = self
+            .integer_key
+            .boolean_bitor(&structural_is(b"}"), &closing_array);
+        let key_start = self.integer_key.boolean_bitand(
+            &self
+                .integer_key
+                .boolean_bitand(&opening_quote, &tokenizer.expect_key),
+            &self
+                .integer_key
+                .scalar_eq_parallelized(&tokenizer.array_depth, 0),
+        );
+        let key_separator = structural_is(b"{,");
+        let token = JsonToken {
+            is_char,
+            delimiter: [&opening_quote, &closing_quote, &escape_start]
+                .into_iter()
+                .fold(self.create_false(), |a, b| {
+                    self.integer_key.boolean_bitor(&a, b)
+                }),
+            escaped_char: self
+                .integer_key
+                .boolean_bitand(&tokenizer.in_string, &tokenizer.escaped),
+            key_start,
+            colon: structural_is(b":"),
+            value_end: self
+                .integer_key
+                .boolean_bitor(&structural_is(b","), &closing_bracket),
+            depth: tokenizer.depth.clone(),
+            structural,
+        };
+
+        // A key is expected after an opening brace or a comma, until the next non whitespace
+        // character outside strings.
+        tokenizer.expect_key = self.integer_key.boolean_bitor(
+            &key_separator,
+            &self.integer_key.boolean_bitand(
+                &tokenizer.expect_key,
+                &self.integer_key.boolean_bitnot(
+                    &self
+                        .integer_key
+                        .boolean_bitand(&token.structural, &self.is_not_ascii_white_space(c)),
+                ),
+            ),
+        );
+        tokenizer.in_string = self.integer_key.boolean_bitor(
+            &opening_quote,
+            &self.integer_key.boolean_bitand(
+                &tokenizer.in_string,
+                &self.integer_key.boolean_bitnot(&closing_quote),
+            ),
+        );
+        tokenizer.escaped = escape_start;
+        let depth_step = |flag: &BooleanBlock| -> RadixCiphertext {
+            flag.clone().into_radix(DEPTH_BLOCKS, &self.integer_key)
+        };
+        self.integer_key
+            .add_assign_parallelized(&mut tokenizer.depth, &depth_step(&opening_bracket));
+        self.integer_key
+            .sub_assign_parallelized(&mut tokenizer.depth, &depth_step(&closing_bracket));
+        self.integer_key
+            .add_assign_parallelized(&mut tokenizer.array_depth, &depth_step(&opening_array));
+        self.integer_key
+            .sub_assign_parallelized(&mut tokenizer.array_depth, &depth_step(&closing_array));
+        token
+    }
+
+    /// Returns an encryption of true if c is one of the clear characters `chars`.
+    fn is_any_clear_char(&self, c: &FheAsciiChar, chars: &[u8]) -> BooleanBlock {
+        chars
+            .iter()
+            .map(|clear_char| self.eq_clear_char(c, *clear_char))
+            .reduce(|a, b| self.integer_key.boolean_bitor(&a, &b))
+            .unwrap()
+    }
+
+    /// Returns the character represented by the JSON escape sequence made of a backslash and c.
+    fn unescape_json_char(&self, c: &FheAsciiChar) -> FheAsciiChar {
+        let mut result = c.0.clone();
+        for (escape, character) in JSON_ESCAPES {
+            result = self.integer_key.cmux_parallelized(
+                &self.eq_clear_char(c, escape),
+                &self.create_n(character),
+                &result,
+            );
+        }
+        FheAsciiChar(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::json::JSON_MAX_DEPTH;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_json_get(document: &str, path: &str, padding: usize, expected: Option<&str>) {
+        let encrypted_document = CLIENT_KEY
+            .encrypt_str_random_padding(document, padding)
+            .unwrap();
+        let (found, value) = SERVER_KEY.json_get(&encrypted_document, path);
+        let result = CLIENT_KEY
+            .decrypt_bool(&found)
+            .then(|| CLIENT_KEY.decrypt_string(&value).unwrap());
+        assert_eq!(result.as_deref(), expected);
+    }
+
+    #[test]
+    fn test_json_get() {
+        let document = r#"{"a": {"b": "x\"y\n", "c": 12}, "b": [{"c": 1}], "d": {"e": {}}}"#;
+        check_json_get(document, "a.b", 2, Some("x\"y\n"));
+        check_json_get(document, "a.c", 0, Some("12"));
+        check_json_get(document, "a", 0, Some(r#"{"b":"x\"y\n","c":12}"#));
+        check_json_get(document, "b", 0, Some(r#"[{"c":1}]"#));
+        check_json_get(document, "b.c", 0, None);
+        check_json_get(document, "c", 0, None);
+        check_json_get(document, "d.e", 1, Some("{}"));
+        check_json_get(r#"{"ab": 1, "a": 2}"#, "a", 0, Some("2"));
+        check_json_get(r#"{"k": "a,b}", "v": true}"#, "v", 0, Some("true"));
+        check_json_get(r#"{"a": {"x": 1}, "b": {"c": 2}}"#, "a.c", 1, None);
+        check_json_get(document, "", 0, None);
+        check_json_get(document, &["a"; JSON_MAX_DEPTH + 1].join("."), 0, None);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::ciphertext::FheString;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// A value of an `FheStringMap`, which can be obliviously selected between two values.
+pub trait FheMapValue: Clone {
+    /// Returns `if_true` if `condition` encrypts true and `if_false` otherwise.
+    fn select(
+        server_key: &StringServerKey,
+        condition: &BooleanBlock,
+        if_true: &Self,
+        if_false: &Self,
+    ) -> Self;
+}
+
+/// Integer values should all have the same number of blocks as the empty value of the map.
+impl FheMapValue for RadixCiphertext {
+    fn select(
+        server_key: &StringServerKey,
+        condition: &BooleanBlock,
+        if_true: &Self,
+        if_false: &Self,
+    ) -> Self {
+        server_key
+            .integer_key
+            .cmux_parallelized(condition, if_true, if_false)
+    }
+}
+
+impl FheMapValue for FheString {
+    fn select(
+        server_key: &StringServerKey,
+        condition: &BooleanBlock,
+        if_true: &Self,
+        if_false: &Self,
+    ) -> Self {
+        server_key.select(condition, if_true, if_false)
+    }
+}
+
+/// A map from encrypted keys to encrypted values, stored in a fixed number of slots. Every
+/// operation reads or writes all the slots with the same sequence of homomorphic operations, so
+/// it does not reveal which slot is touched nor how many slots are used.
+pub struct FheStringMap<V: FheMapValue> {
+    /// Encryption of true for each slot holding an entry.
+    pub occupied: Vec<BooleanBlock>,
+    pub keys: Vec<FheString>,
+    pub values: Vec<V>,
+    /// The value of the empty slots, also returned by `get` for a missing key.
+    empty_value: V,
+}
+
+impl StringServerKey {
+    /// Creates an `FheStringMap` with `capacity` empty slots. The `empty_value` is returned by
+    /// `get` when the key is missing.
+    pub fn new_string_map<V: FheMapValue>(
+        &self,
+        capacity: usize,
+        empty_value: V,
+    ) -> FheStringMap<V> {
+        FheStringMap {
+            occupied: vec![self.create_false(); capacity],
+            keys: vec![self.server_encrypt_str("").unwrap(); capacity],
+            values: vec![empty_value.clone(); capacity],
+            empty_value,
+        }
+    }
+}
+
+impl<V: FheMapValue> FheStringMap<V> {
+    /// Returns an FheOption of the value of `key`, an encryption of (true, v) if the map contains
+    /// `key` with the value v, an encryption of (false, empty_value) otherwise.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let mut map = server_key.new_string_map(2, server_key.create_zero());
+    /// let key = client_key.encrypt_str("alice").unwrap();
+    /// map.insert_or_update(&server_key, &key, &client_key.encrypt_integer(7));
+    /// let (found, value) = map.get(&server_key, &client_key.encrypt_str("alice").unwrap());
+    /// assert_eq!(client_key.decrypt_integer(&server_key.bool_to_radix(&found)), 1);
+    /// assert_eq!(client_key.decrypt_integer(&value), 7);
+    /// ```
+    pub fn get(&self, server_key: &StringServerKey, key: &FheString) -> (BooleanBlock, V) {
+        let matches = self.matching_slots(server_key, key);
+        let mut found = server_key.create_false();
+        let mut value = self.empty_value.clone();
+        // At most one slot matches, as keys are inserted only once.
+        for (slot_match, slot_value) in matches.iter().zip(&self.values) {
+            value = V::select(server_key, slot_match, slot_value, &value);
+            server_key
+                .integer_key
+                .boolean_bitor_assign(&mut found, slot_match);
+        }
+        (found, value)
+    }
+
+    /// Returns an encryption of true if the map contains `key`.
+    pub fn contains_key(&self, server_key: &StringServerKey, key: &FheString) -> BooleanBlock {
+        self.matching_slots(server_key, key)
+            .into_iter()
+            .fold(server_key.create_false(), |a, b| {
+                server_key.integer_key.boolean_bitor(&a, &b)
+            })
+    }
+
+    /// Sets the value of `key` to `value`. If the map does not contain `key`, the entry is
+    /// written in the first empty slot. Returns an encryption of false if the map was full and did
+    /// not contain `key`, in which case the map is unchanged, and an encryption of true otherwise.
+    /// The keys of the slots are padded to the content length of the longest inserted key.
+    pub fn insert_or_update(
+        &mut self,
+        server_key: &StringServerKey,
+        key: &FheString,
+        value: &V,
+    ) -> BooleanBlock {
+        let matches = self.matching_slots(server_key, key);
+        let exists = matches.iter().fold(server_key.create_false(), |a, b| {
+            server_key.integer_key.boolean_bitor(&a, b)
+        });
+        let mut empty_slot_before = server_key.create_false();
+        for (i, slot_match) in matches.iter().enumerate() {
+            let is_empty = server_key.integer_key.boolean_bitnot(&self.occupied[i]);
+            // The entry is written in the matching slot, or in the first empty slot if no slot
+            // matches.
+            let is_first_empty = server_key.integer_key.boolean_bitand(
+                &is_empty,
+                &server_key.integer_key.boolean_bitnot(
+                    &server_key
+                        .integer_key
+                        .boolean_bitor(&exists, &empty_slot_before),
+                ),
+            );
+            let is_written = server_key
+                .integer_key
+                .boolean_bitor(slot_match, &is_first_empty);
+            self.keys[i] = server_key.select(&is_written, key, &self.keys[i]);
+            self.values[i] = V::select(server_key, &is_written, value, &self.values[i]);
+            server_key
+                .integer_key
+                .boolean_bitor_assign(&mut self.occupied[i], &is_written);
+            server_key
+                .integer_key
+                .boolean_bitor_assign(&mut empty_slot_before, &is_empty);
+        }
+        server_key
+            .integer_key
+            .boolean_bitor(&exists, &empty_slot_before)
+    }
+
+    /// For each slot, an encryption of true if it holds an entry with the key `key`.
+    fn matching_slots(&self, server_key: &StringServerKey, key: &FheString) -> Vec<BooleanBlock> {
+        self.occupied
+            .iter()
+            .zip(&self.keys)
+            .map(|(occupied, slot_key)| {
+                server_key
+                    .integer_key
+                    .boolean_bitand(occupied, &server_key.eq_encrypted(slot_key, key))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::map::FheStringMap;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+    use tfhe::integer::RadixCiphertext;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_get(map: &FheStringMap<RadixCiphertext>, key: &str, expected: Option<u32>) {
+        let encrypted_key = CLIENT_KEY.encrypt_str_random_padding(key, 1).unwrap();
+        let (found, value) = map.get(&SERVER_KEY, &encrypted_key);
+        let clear_found = CLIENT_KEY.decrypt_bool(&found);
+        let result = clear_found.then(|| CLIENT_KEY.decrypt_integer(&value));
+        assert_eq!(result, expected);
+        let contains = map.contains_key(&SERVER_KEY, &encrypted_key);
+        let clear_contains = CLIENT_KEY.decrypt_bool(&contains);
+        assert_eq!(clear_contains, expected.is_some());
+    }
+
+    #[test]
+    fn test_string_map() {
+        let mut map = SERVER_KEY.new_string_map(2, SERVER_KEY.create_zero());
+        for (key, value, expected_success) in [("ab", 1u8, true), ("", 2, true), ("ab", 3, true)] {
+            let encrypted_key = CLIENT_KEY.encrypt_str_random_padding(key, 2).unwrap();
+            let encrypted_value = CLIENT_KEY.encrypt_integer(value);
+            let success = map.insert_or_update(&SERVER_KEY, &encrypted_key, &encrypted_value);
+            let clear_success = CLIENT_KEY.decrypt_bool(&success);
+            assert_eq!(clear_success, expected_success);
+        }
+        // The map is full.
+        let encrypted_key = CLIENT_KEY.encrypt_str("c").unwrap();
+        let success = map.insert_or_update(&SERVER_KEY, &encrypted_key, &SERVER_KEY.create_n(4));
+        assert!(!CLIENT_KEY.decrypt_bool(&success));
+
+        check_get(&map, "ab", Some(3));
+        check_get(&map, "", Some(2));
+        check_get(&map, "c", None);
+        check_get(&map, "a", None);
+    }
+}
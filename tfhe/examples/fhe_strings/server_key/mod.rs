@@ -1,36 +1,57 @@
 mod accessors;
 mod add;
+mod align;
+mod batch;
+mod bytes;
 mod change_case;
 mod change_padding;
-mod comparisons;
+mod clear_haystack;
+mod collections;
+pub mod comparisons;
 mod contains;
+pub mod counting;
+mod csv;
+mod encoding;
 mod ends_with;
 pub mod find;
+mod ignore_case;
 pub mod is_empty;
+pub mod json;
 mod length_arithmetic;
+pub mod map;
+mod multi_pattern;
+mod packed;
 mod repeat;
 mod replace;
 mod rsplit;
 mod rsplitn;
+pub mod scanner;
+pub mod similarity;
 pub mod split;
 mod split_ascii_whitespace;
+pub mod split_bounded;
 mod split_inclusive;
 mod split_terminator;
 mod starts_with;
 pub mod strip;
 mod substring;
 mod trim;
+mod validators;
+mod word_counts;
 
+use crate::server_key::counting::CountingServerKey;
 use serde::{Deserialize, Serialize};
 use tfhe::integer::ServerKey;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StringServerKey {
-    pub integer_key: ServerKey,
+    pub integer_key: CountingServerKey,
 }
 
 impl From<ServerKey> for StringServerKey {
     fn from(integer_key: ServerKey) -> Self {
-        Self { integer_key }
+        Self {
+            integer_key: integer_key.into(),
+        }
     }
 }
@@ -0,0 +1,409 @@
+use crate::ciphertext::{FheAsciiChar, FheString, Padding, NUMBER_BLOCKS};
+use crate::server_key::StringServerKey;
+use std::collections::HashMap;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// FHE version of Option<(usize, usize)> for a search among several patterns, an encryption of
+/// (true, i, n) corresponds to Some((i, n)) where i is the index of the matched pattern in the
+/// dictionary and n the index where it starts. An encryption of (false, _, _) corresponds to None.
+pub type FheOptionPatternMatch = (BooleanBlock, RadixCiphertext, RadixCiphertext);
+
+/// FHE version of Option<u64> for a lookup in a clear table, an encryption of (true, v)
+/// corresponds to Some(v) and an encryption of (false, 0) to None.
+pub type FheOptionValue = (BooleanBlock, RadixCiphertext);
+
+/// A trie over a dictionary of clear patterns. Patterns with a common prefix share the nodes of
+/// this prefix, so that the comparisons of the prefix with the string are done only once.
+#[derive(Default)]
+pub struct ClearTrie {
+    children: Vec<(u8, ClearTrie)>,
+    /// Smallest index in the dictionary of the patterns ending at this node.
+    pattern_index: Option<usize>,
+}
+
+impl ClearTrie {
+    pub fn new(patterns: &[&str]) -> Self {
+        let mut trie = ClearTrie::default();
+        for (index, pattern) in patterns.iter().enumerate() {
+            trie.insert(pattern.as_bytes(), index);
+        }
+        trie
+    }
+
+    fn insert(&mut self, pattern: &[u8], index: usize) {
+        match pattern.split_first() {
+            None => {
+                self.pattern_index.get_or_insert(index);
+            }
+            Some((first, rest)) => {
+                let position = match self.children.iter().position(|(c, _)| c == first) {
+                    Some(position) => position,
+                    None => {
+                        self.children.push((*first, ClearTrie::default()));
+                        self.children.len() - 1
+                    }
+                };
+                self.children[position].1.insert(rest, index);
+            }
+        }
+    }
+}
+
+/// Cache of the comparisons between the characters of a string and clear characters, shared
+/// between all the patterns and all the starting indices of a search.
+struct EqualityCache<'a> {
+    content: &'a [FheAsciiChar],
+    equalities: HashMap<(usize, u8), BooleanBlock>,
+}
+
+impl<'a> EqualityCache<'a> {
+    fn new(content: &'a [FheAsciiChar]) -> Self {
+        EqualityCache {
+            content,
+            equalities: HashMap::new(),
+        }
+    }
+
+    fn eq(&mut self, server_key: &StringServerKey, index: usize, c: u8) -> BooleanBlock {
+        let content = self.content;
+        self.equalities
+            .entry((index, c))
+            .or_insert_with(|| server_key.eq_clear_char(&content[index], c))
+            .clone()
+    }
+}
+
+impl StringServerKey {
+    /// Returns an encryption of true if s contains at least one of the clear patterns. The
+    /// comparisons of characters are shared between the patterns.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a secret message").unwrap();
+    /// let result = server_key.contains_any(&encrypted_str, &["password", "secret"]);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn contains_any(&self, s: &FheString, patterns: &[&str]) -> BooleanBlock {
+        let trie = ClearTrie::new(patterns);
+        if trie.pattern_index.is_some() {
+            return self.create_true();
+        }
+        match s.padding {
+            // Push padding to the end if necessary, the string s can have at worst initial and
+            // final padding zeros.
+            Padding::Anywhere => self.connected_contains_any(&self.push_padding_to_end(s), &trie),
+            _ => self.connected_contains_any(s, &trie),
+        }
+    }
+
+    /// Searches for the first occurence of any of the clear patterns in s. Returns an
+    /// FheOptionPatternMatch Some((i, n)) if the pattern of index i in `patterns` first starts at
+    /// index n, None otherwise. If several patterns start at the first matching index, the one of
+    /// smallest index in `patterns` is returned. The pattern index is encrypted on enough blocks
+    /// for the largest index of `patterns`, and at least on the same number of blocks as lengths.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a secret password").unwrap();
+    /// let result = server_key.find_any(&encrypted_str, &["password", "secret"]);
+    /// let clear_result_bool = client_key.decrypt_integer(&server_key.bool_to_radix(&result.0));
+    /// assert_eq!(clear_result_bool, 1);
+    /// assert_eq!(client_key.decrypt_integer(&result.1), 1);
+    /// assert_eq!(client_key.decrypt_integer(&result.2), 2);
+    /// ```
+    pub fn find_any(&self, s: &FheString, patterns: &[&str]) -> FheOptionPatternMatch {
+        let trie = ClearTrie::new(patterns);
+        let max_index = patterns.len().saturating_sub(1) as u64;
+        let index_bits = (u64::BITS - max_index.leading_zeros()) as usize;
+        let index_blocks = std::cmp::max(NUMBER_BLOCKS, (index_bits + 1) / 2);
+        match (s.padding, trie.pattern_index) {
+            (Padding::Anywhere, _) => {
+                self.connected_find_any(&self.push_padding_to_end(s), &trie, index_blocks)
+            }
+            // The empty pattern matches at index 0, the other patterns matching there have to be
+            // compared to the first characters of s, so s should not have initial padding.
+            (Padding::Initial | Padding::InitialAndFinal, Some(_)) => {
+                self.connected_find_any(&self.push_padding_to_end(s), &trie, index_blocks)
+            }
+            _ => self.connected_find_any(s, &trie, index_blocks),
+        }
+    }
+
+    /// Returns an encryption of true if s is equal to one of the clear keys. The keys are stored
+    /// in a trie, so that the comparisons of the characters of s with the common prefixes of the
+    /// keys are done only once.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("FR").unwrap();
+    /// let result = server_key.is_in(&encrypted_str, &["DE", "FI", "FR"]);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_in(&self, s: &FheString, keys: &[&str]) -> BooleanBlock {
+        self.trie_exact_matches(s, keys)
+            .into_iter()
+            .map(|(_, current_match)| current_match)
+            .reduce(|a, b| self.integer_key.boolean_bitor(&a, &b))
+            .unwrap_or_else(|| self.create_false())
+    }
+
+    /// Looks up s in a clear table of (key, value) pairs. Returns an FheOptionValue Some(v) if s
+    /// is equal to a key of the table, v being the value of its first occurrence, None otherwise.
+    /// The value is encrypted on enough blocks for the largest value of the table, and at least on
+    /// the same number of blocks as lengths. The keys are stored in a trie, see `is_in`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("FR").unwrap();
+    /// let table = [("DE", 49), ("FI", 358), ("FR", 33)];
+    /// let (found, value) = server_key.lookup(&encrypted_str, &table);
+    /// assert_eq!(client_key.decrypt_integer(&server_key.bool_to_radix(&found)), 1);
+    /// assert_eq!(client_key.decrypt_integer(&value), 33);
+    /// ```
+    pub fn lookup(&self, s: &FheString, table: &[(&str, u64)]) -> FheOptionValue {
+        let keys: Vec<&str> = table.iter().map(|(key, _)| *key).collect();
+        let max_value = table.iter().map(|(_, value)| *value).max().unwrap_or(0);
+        let value_bits = (u64::BITS - max_value.leading_zeros()) as usize;
+        let number_blocks = std::cmp::max(NUMBER_BLOCKS, (value_bits + 1) / 2);
+
+        let mut found = self.create_false();
+        let mut value: RadixCiphertext = self.integer_key.create_trivial_zero_radix(number_blocks);
+        // At most one key matches, as the trie keeps only the first occurrence of each key.
+        for (index, current_match) in self.trie_exact_matches(s, &keys) {
+            value = self.integer_key.cmux_parallelized(
+                &current_match,
+                &self
+                    .integer_key
+                    .create_trivial_radix(table[index].1, number_blocks),
+                &value,
+            );
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &current_match);
+        }
+        (found, value)
+    }
+
+    /// Walks the trie of the keys along s. Returns the index of the first occurrence of each key,
+    /// with an encryption of true if s is equal to this key.
+    fn trie_exact_matches(&self, s: &FheString, keys: &[&str]) -> Vec<(usize, BooleanBlock)> {
+        let trie = ClearTrie::new(keys);
+        let s = match s.padding {
+            Padding::None | Padding::Final => s.clone(),
+            _ => self.push_padding_to_end(s),
+        };
+        if s.content.is_empty() {
+            return trie
+                .pattern_index
+                .map(|index| vec![(index, self.create_true())])
+                .unwrap_or_default();
+        }
+        let mut cache = EqualityCache::new(&s.content);
+        self.trie_matches_at(&mut cache, &trie, 0)
+            .into_iter()
+            .map(|(index, prefix_match)| {
+                // s is equal to the key if the key is followed by the end of s or padding zeros.
+                let key_length = keys[index].len();
+                match key_length < s.content.len() {
+                    true => (
+                        index,
+                        self.integer_key
+                            .boolean_bitand(&prefix_match, &cache.eq(self, key_length, 0)),
+                    ),
+                    false => (index, prefix_match),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns an encryption of true if s contains one of the patterns of the trie. It assumes that
+    /// s is connected (its padding is at worst Initial and Final).
+    fn connected_contains_any(&self, s: &FheString, trie: &ClearTrie) -> BooleanBlock {
+        let mut cache = EqualityCache::new(&s.content);
+        let mut result = self.create_false();
+        for n in 0..s.content.len() {
+            for (_, current_match) in self.trie_matches_at(&mut cache, trie, n) {
+                self.integer_key
+                    .boolean_bitor_assign(&mut result, &current_match);
+            }
+        }
+        result
+    }
+
+    /// Searches for the first occurence of one of the patterns of the trie in s. It assumes that s
+    /// is connected (its padding is at worst Initial and Final). The pattern index is encrypted on
+    /// `index_blocks` blocks.
+    fn connected_find_any(
+        &self,
+        s: &FheString,
+        trie: &ClearTrie,
+        index_blocks: usize,
+    ) -> FheOptionPatternMatch {
+        let create_index = |i: usize| -> RadixCiphertext {
+            self.integer_key
+                .create_trivial_radix(i as u64, index_blocks)
+        };
+        if s.content.is_empty() {
+            return match trie.pattern_index {
+                Some(index) => (self.create_true(), create_index(index), self.create_zero()),
+                None => (self.create_false(), create_index(0), self.create_zero()),
+            };
+        }
+        let mut cache = EqualityCache::new(&s.content);
+        let mut found = self.create_false();
+        let mut pattern_index = create_index(0);
+        let mut index = self.create_zero();
+        for n in 0..s.content.len() {
+            // Among the patterns matching at n, keep the one of smallest index.
+            let mut matches = self.trie_matches_at(&mut cache, trie, n);
+            matches.sort_by_key(|(i, _)| std::cmp::Reverse(*i));
+            let mut current_found = self.create_false();
+            let mut current_pattern_index = create_index(0);
+            for (i, current_match) in matches {
+                current_pattern_index = self.integer_key.cmux_parallelized(
+                    &current_match,
+                    &create_index(i),
+                    &current_pattern_index,
+                );
+                self.integer_key
+                    .boolean_bitor_assign(&mut current_found, &current_match);
+            }
+
+            pattern_index =
+                self.integer_key
+                    .cmux_parallelized(&found, &pattern_index, &current_pattern_index);
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &current_found);
+            let increment_index = self.increment_index(s, n, &found);
+            self.integer_key
+                .add_assign_parallelized(&mut index, &increment_index);
+        }
+        (found, pattern_index, index)
+    }
+
+    /// Walks the trie along the content of the string starting at index n. Returns the index of
+    /// each pattern of the trie, with an encryption of true if it matches the content at index n.
+    fn trie_matches_at(
+        &self,
+        cache: &mut EqualityCache,
+        trie: &ClearTrie,
+        n: usize,
+    ) -> Vec<(usize, BooleanBlock)> {
+        let mut matches = Vec::new();
+        if let Some(pattern_index) = trie.pattern_index {
+            matches.push((pattern_index, self.create_true()));
+        }
+        let mut to_visit: Vec<(&ClearTrie, usize, BooleanBlock)> = trie
+            .children
+            .iter()
+            .map(|(c, child)| (child, n, cache.eq(self, n, *c)))
+            .collect();
+        while let Some((node, index, prefix_match)) = to_visit.pop() {
+            if let Some(pattern_index) = node.pattern_index {
+                matches.push((pattern_index, prefix_match.clone()));
+            }
+            if index + 1 >= cache.content.len() {
+                continue;
+            }
+            for (c, child) in node.children.iter() {
+                let child_match = self
+                    .integer_key
+                    .boolean_bitand(&prefix_match, &cache.eq(self, index + 1, *c));
+                to_visit.push((child, index + 1, child_match));
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn clear_find_any(s: &str, patterns: &[&str]) -> Option<(usize, usize)> {
+        (0..=s.len()).find_map(|n| {
+            patterns
+                .iter()
+                .position(|pattern| s[n..].starts_with(pattern))
+                .map(|i| (i, n))
+        })
+    }
+
+    fn check_any(s: &str, padding: usize, patterns: &[&str]) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let expected = clear_find_any(s, patterns);
+
+        let contains = SERVER_KEY.contains_any(&encrypted_s, patterns);
+        let clear_contains = CLIENT_KEY.decrypt_bool(&contains);
+        assert_eq!(clear_contains, expected.is_some());
+
+        let (found, pattern_index, index) = SERVER_KEY.find_any(&encrypted_s, patterns);
+        let clear_found = CLIENT_KEY.decrypt_bool(&found);
+        let result = clear_found.then(|| {
+            (
+                CLIENT_KEY.decrypt_integer(&pattern_index) as usize,
+                CLIENT_KEY.decrypt_integer(&index) as usize,
+            )
+        });
+        assert_eq!(result, expected);
+    }
+
+    fn check_lookup(s: &str, padding: usize, table: &[(&str, u64)]) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let expected = table.iter().find(|(key, _)| *key == s).map(|(_, v)| *v);
+        let keys: Vec<&str> = table.iter().map(|(key, _)| *key).collect();
+
+        let is_in = SERVER_KEY.is_in(&encrypted_s, &keys);
+        let clear_is_in = CLIENT_KEY.decrypt_bool(&is_in);
+        assert_eq!(clear_is_in, expected.is_some());
+
+        let (found, value) = SERVER_KEY.lookup(&encrypted_s, table);
+        let clear_found = CLIENT_KEY.decrypt_bool(&found);
+        let result = clear_found.then(|| CLIENT_KEY.decrypt_integer(&value) as u64);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let table = [("ab", 1000), ("a", 2), ("abc", 3), ("b", 4), ("ab", 5)];
+        check_lookup("ab", 2, &table);
+        check_lookup("a", 1, &table);
+        check_lookup("abc", 0, &table);
+        check_lookup("abd", 0, &table);
+        check_lookup("c", 1, &table);
+        check_lookup("", 1, &table);
+        check_lookup("", 0, &[("a", 1), ("", 7)]);
+        check_lookup("a", 0, &[]);
+    }
+
+    #[test]
+    fn test_find_any() {
+        check_any("abcab", 0, &["ca", "bc", "abd"]);
+        check_any("abcab", 2, &["cab", "ca", "c"]);
+        check_any("abcab", 1, &["ab", "a"]);
+        check_any("abcab", 1, &["abd", "bb", "x"]);
+        check_any("abc", 1, &["d", ""]);
+        check_any("abc", 1, &["ab", "", "a"]);
+        check_any("", 2, &["a"]);
+        // The pattern index does not fit on the number of blocks of lengths.
+        let patterns: Vec<String> = (0..300u16)
+            .map(|i| String::from_utf8(vec![b'a' + (i / 26) as u8, b'a' + (i % 26) as u8]).unwrap())
+            .collect();
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        check_any("ln", 0, &patterns);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::ciphertext::{
+    FheAsciiChar, FhePackedString, FheStrLength, FheString, Padding, CHARS_PER_WORD, NUMBER_BLOCKS,
+};
+use crate::server_key::find::FheOptionInt;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, IntegerCiphertext, RadixCiphertext};
+
+impl StringServerKey {
+    /// Packs the characters of s in words of `CHARS_PER_WORD` characters. Packing only moves
+    /// blocks around, the only homomorphic operations are the ones needed to push the padding of s
+    /// to the end.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a string of 21 chars.").unwrap();
+    /// let packed = server_key.pack(&encrypted_str);
+    /// assert_eq!(packed.words.len(), 3);
+    /// let unpacked = server_key.unpack(&packed);
+    /// assert_eq!(client_key.decrypt_string(&unpacked).unwrap(), "a string of 21 chars.");
+    /// ```
+    pub fn pack(&self, s: &FheString) -> FhePackedString {
+        let final_padding_s;
+        let s = match s.padding {
+            Padding::None | Padding::Final => s,
+            _ => {
+                final_padding_s = self.push_padding_to_end(s);
+                &final_padding_s
+            }
+        };
+        let zero = FheAsciiChar(self.create_zero());
+        let words = s
+            .content
+            .chunks(CHARS_PER_WORD)
+            .map(|chars| {
+                // Blocks are stored from the least significant, so from the last character.
+                let blocks = (0..CHARS_PER_WORD)
+                    .rev()
+                    .flat_map(|i| chars.get(i).unwrap_or(&zero).0.blocks().to_vec())
+                    .collect::<Vec<_>>();
+                RadixCiphertext::from(blocks)
+            })
+            .collect();
+        FhePackedString {
+            words,
+            length: s.length.clone(),
+        }
+    }
+
+    /// Unpacks the words of `packed` into an `FheString`, with at worst final padding.
+    pub fn unpack(&self, packed: &FhePackedString) -> FheString {
+        let mut content: Vec<FheAsciiChar> = packed
+            .words
+            .iter()
+            .flat_map(|word| {
+                word.blocks()
+                    .chunks(NUMBER_BLOCKS)
+                    .rev()
+                    .map(|blocks| FheAsciiChar(RadixCiphertext::from(blocks.to_vec())))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        match packed.length {
+            FheStrLength::Clear(length) => {
+                content.truncate(length);
+                FheString {
+                    content,
+                    padding: Padding::None,
+                    length: packed.length.clone(),
+                }
+            }
+            FheStrLength::Encrypted(_) => FheString {
+                content,
+                padding: Padding::Final,
+                length: packed.length.clone(),
+            },
+        }
+    }
+
+    /// Checks if the packed strings s1 and s2 are equal, with one comparison per word.
+    pub fn eq_packed(&self, s1: &FhePackedString, s2: &FhePackedString) -> BooleanBlock {
+        let mut result = self.create_true();
+        for k in 0..std::cmp::max(s1.words.len(), s2.words.len()) {
+            let word_eq = match (s1.words.get(k), s2.words.get(k)) {
+                (Some(word1), Some(word2)) => self.integer_key.eq_parallelized(word1, word2),
+                // The extra words of the longest content should be padding.
+                (Some(word), None) | (None, Some(word)) => {
+                    self.integer_key.scalar_eq_parallelized(word, 0u64)
+                }
+                (None, None) => unreachable!(),
+            };
+            self.integer_key
+                .boolean_bitand_assign(&mut result, &word_eq);
+        }
+        result
+    }
+
+    /// Checks if the packed string s1 is equal to the clear string s2, with one scalar comparison
+    /// per word.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str_padding("packed string", 3).unwrap();
+    /// let packed = server_key.pack(&encrypted_str);
+    /// let result = server_key.eq_packed_clear(&packed, "packed string");
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn eq_packed_clear(&self, s1: &FhePackedString, s2: &str) -> BooleanBlock {
+        if s2.len() > s1.words.len() * CHARS_PER_WORD {
+            return self.create_false();
+        }
+        let clear_words: Vec<u64> = s2
+            .as_bytes()
+            .chunks(CHARS_PER_WORD)
+            .map(|chars| StringServerKey::pack_clear_chars(chars, CHARS_PER_WORD))
+            .collect();
+        let mut result = self.create_true();
+        for (k, word) in s1.words.iter().enumerate() {
+            // The words after the end of s2 should be padding.
+            let clear_word = clear_words.get(k).copied().unwrap_or(0);
+            self.integer_key.boolean_bitand_assign(
+                &mut result,
+                &self.integer_key.scalar_eq_parallelized(word, clear_word),
+            );
+        }
+        result
+    }
+
+    /// Checks if the packed string s starts with the clear string prefix, with one scalar
+    /// comparison per word of the prefix.
+    pub fn starts_with_packed_clear(&self, s: &FhePackedString, prefix: &str) -> BooleanBlock {
+        self.words_start_with_clear(&s.words, prefix.as_bytes())
+    }
+
+    /// Searches for the clear pattern in s, only at indices multiple of `CHARS_PER_WORD`. Returns
+    /// an FheOptionInt Some(n) if pattern is found and first starts at the aligned index n, None
+    /// otherwise. Matches at unaligned indices are ignored.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("id=0001 id=0002 ").unwrap();
+    /// let packed = server_key.pack(&encrypted_str);
+    /// let result = server_key.find_packed_clear_aligned(&packed, "id=0002");
+    /// let clear_result_bool = client_key.decrypt_integer(&server_key.bool_to_radix(&result.0));
+    /// assert_eq!(clear_result_bool, 1);
+    /// let clear_result_index = client_key.decrypt_integer(&result.1);
+    /// assert_eq!(clear_result_index, 8);
+    /// ```
+    pub fn find_packed_clear_aligned(&self, s: &FhePackedString, pattern: &str) -> FheOptionInt {
+        if pattern.is_empty() {
+            return (self.create_true(), self.create_zero());
+        }
+        let mut found = self.create_false();
+        let mut index = self.create_zero();
+        // The words are scanned backwards, so the cmux of the first matching word is applied last.
+        for k in (0..s.words.len()).rev() {
+            let current_match = self.words_start_with_clear(&s.words[k..], pattern.as_bytes());
+            index = self.integer_key.cmux_parallelized(
+                &current_match,
+                &self.create_n((k * CHARS_PER_WORD) as u8),
+                &index,
+            );
+            self.integer_key
+                .boolean_bitor_assign(&mut found, &current_match);
+        }
+        (found, index)
+    }
+
+    /// Checks if the packed words start with the clear prefix. The last word of the prefix can be
+    /// partial, in this case only the blocks of its first characters are compared.
+    fn words_start_with_clear(&self, words: &[RadixCiphertext], prefix: &[u8]) -> BooleanBlock {
+        if prefix.len() > words.len() * CHARS_PER_WORD {
+            return self.create_false();
+        }
+        let mut result = self.create_true();
+        for (word, chars) in words.iter().zip(prefix.chunks(CHARS_PER_WORD)) {
+            let first_chars = RadixCiphertext::from(
+                word.blocks()[(CHARS_PER_WORD - chars.len()) * NUMBER_BLOCKS..].to_vec(),
+            );
+            self.integer_key.boolean_bitand_assign(
+                &mut result,
+                &self.integer_key.scalar_eq_parallelized(
+                    &first_chars,
+                    StringServerKey::pack_clear_chars(chars, chars.len()),
+                ),
+            );
+        }
+        result
+    }
+
+    /// Packs at most `CHARS_PER_WORD` clear characters in an integer of `word_length` characters,
+    /// the first character being the most significant.
+    fn pack_clear_chars(chars: &[u8], word_length: usize) -> u64 {
+        (0..word_length).fold(0, |word, i| {
+            (word << 8) + chars.get(i).copied().unwrap_or(0) as u64
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let s = CLIENT_KEY
+            .encrypt_str_random_padding("hello packed world", 3)
+            .unwrap();
+        let packed = SERVER_KEY.pack(&s);
+        let unpacked = SERVER_KEY.unpack(&packed);
+        assert_eq!(
+            CLIENT_KEY.decrypt_string(&unpacked).unwrap(),
+            "hello packed world"
+        );
+    }
+
+    #[test]
+    fn test_eq_packed() {
+        let s1 = SERVER_KEY.pack(&CLIENT_KEY.encrypt_str_padding("abcdefghij", 7).unwrap());
+        let s2 = SERVER_KEY.pack(&CLIENT_KEY.encrypt_str("abcdefghij").unwrap());
+        let s3 = SERVER_KEY.pack(&CLIENT_KEY.encrypt_str("abcdefghi").unwrap());
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_packed(&s1, &s2)));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_packed(&s1, &s3)));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_packed_clear(&s1, "abcdefghij")));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_packed_clear(&s1, "abcdefghi")));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.eq_packed_clear(&s3, "abcdefghij")));
+    }
+
+    #[test]
+    fn test_starts_with_find_packed() {
+        let s = SERVER_KEY.pack(
+            &CLIENT_KEY
+                .encrypt_str_padding("key=abc key=def ", 2)
+                .unwrap(),
+        );
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_packed_clear(&s, "key=abc k")));
+        assert!(CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_packed_clear(&s, "")));
+        assert!(!CLIENT_KEY.decrypt_bool(&SERVER_KEY.starts_with_packed_clear(&s, "key=def")));
+
+        let (found, index) = SERVER_KEY.find_packed_clear_aligned(&s, "key=def");
+        assert!(CLIENT_KEY.decrypt_bool(&found));
+        assert_eq!(CLIENT_KEY.decrypt_integer(&index), 8);
+        // "=abc" only occurs at an unaligned index.
+        let (found, _) = SERVER_KEY.find_packed_clear_aligned(&s, "=abc");
+        assert!(!CLIENT_KEY.decrypt_bool(&found));
+    }
+}
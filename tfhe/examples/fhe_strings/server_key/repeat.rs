@@ -41,7 +41,7 @@ impl StringServerKey {
     /// assert_eq!(decrypted_result, "aa");
     /// ```
     pub fn repeat_encrypted(&self, s: &FheString, n: &RadixCiphertext, n_max: usize) -> FheString {
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
 
         let result_padding = match s.padding {
             Padding::None => Padding::None,
@@ -55,12 +55,7 @@ impl StringServerKey {
             for c in &s.content {
                 let repeat_again: BooleanBlock =
                     self.integer_key.scalar_gt_parallelized(n, i as u64);
-                let new_encrypted_char = FheAsciiChar(self.integer_key.cmux_parallelized(
-                    &repeat_again,
-                    &c.0,
-                    &zero,
-                ));
-                result_content.push(new_encrypted_char);
+                result_content.push(self.cmux_char(&repeat_again, c, &zero));
             }
         }
         FheString {
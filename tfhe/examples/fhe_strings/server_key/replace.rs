@@ -91,7 +91,7 @@ impl StringServerKey {
             length: ClearOrEncrypted::Clear(0),
             padding: Padding::None,
         };
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
         for (i, part) in fhe_split.parts.iter().enumerate() {
             let part_if_in_range = self.cmux_empty_string(
                 &self
@@ -103,8 +103,7 @@ impl StringServerKey {
             let in_range = self
                 .integer_key
                 .scalar_gt_parallelized(&fhe_split.number_parts, (i + 1) as u32);
-            let new_if_in_range =
-                FheAsciiChar(self.integer_key.cmux_parallelized(&in_range, &new.0, &zero));
+            let new_if_in_range = self.cmux_char(&in_range, new, &zero);
             result = self.add(result, &new_if_in_range);
         }
         result
@@ -182,7 +182,7 @@ impl StringServerKey {
         };
         parts.push(empty_string.clone());
         for c in s.content.iter().rev() {
-            let current_char_non_null = self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let current_char_non_null = self.ne_clear_char(c, 0);
             parts.push(FheString {
                 padding: Padding::Final,
                 length: ClearOrEncrypted::Encrypted(self.bool_to_radix(&current_char_non_null)),
@@ -217,7 +217,7 @@ impl StringServerKey {
         };
         //        parts.push(empty_string.clone());
         for c in s.content.iter().rev() {
-            let current_char_non_null = self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let current_char_non_null = self.ne_clear_char(c, 0);
             parts.push(FheString {
                 padding: Padding::Final,
                 length: ClearOrEncrypted::Encrypted(self.bool_to_radix(&current_char_non_null)),
@@ -404,13 +404,9 @@ impl StringServerKey {
                 content: vec![s.content[s.content.len() - 1 - i].clone()],
                 length: match s.len() {
                     FheStrLength::Clear(_) => FheStrLength::Clear(1),
-                    _ => FheStrLength::Encrypted(
-                        self.bool_to_radix(
-                            &self
-                                .integer_key
-                                .scalar_ne_parallelized(&s.content[s.content.len() - 1 - i].0, 0),
-                        ),
-                    ),
+                    _ => FheStrLength::Encrypted(self.bool_to_radix(
+                        &self.ne_clear_char(&s.content[s.content.len() - 1 - i], 0),
+                    )),
                 },
                 padding: match s.len() {
                     FheStrLength::Clear(_) => Padding::None,
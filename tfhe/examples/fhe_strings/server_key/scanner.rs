@@ -0,0 +1,208 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding, NUMBER_BLOCKS};
+use crate::pattern::FheSlicePattern;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// Number of blocks of the offsets and counters of an `FheStringScanner`. A stream is much longer
+/// than a single string, so 32 bits are used instead of the 8 bits of string lengths.
+pub const SCANNER_NUMBER_BLOCKS: usize = 16;
+
+/// The state of a search for a clear pattern in an encrypted input received chunk by chunk. It
+/// keeps the last `pattern.len() - 1` characters of the input, so that matches straddling the
+/// boundary between two chunks are found.
+pub struct FheStringScanner {
+    pub pattern: String,
+    /// The last `pattern.len() - 1` characters of the input, preceded by zeros while fewer
+    /// characters have been consumed.
+    pub tail: Vec<FheAsciiChar>,
+    /// Number of characters consumed so far, padding zeros excluded.
+    pub consumed: RadixCiphertext,
+    /// Encryption of true if the pattern occurs in the input consumed so far.
+    pub contains: BooleanBlock,
+    /// Offset of the first match, meaningful only if `contains` is true.
+    pub first_match: RadixCiphertext,
+    /// Number of non overlapping occurences of the pattern in the input consumed so far.
+    pub count: RadixCiphertext,
+    /// Smallest offset where a match can start without overlapping the last counted match.
+    next_allowed_offset: RadixCiphertext,
+}
+
+impl StringServerKey {
+    /// Creates a scanner searching for the clear pattern in a stream of `FheString` chunks.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let mut scanner = server_key.new_scanner("error");
+    /// for chunk in ["an er", "ror, an", "other error"] {
+    ///     let encrypted_chunk = client_key.encrypt_str(chunk).unwrap();
+    ///     server_key.scan(&mut scanner, &encrypted_chunk);
+    /// }
+    /// assert_eq!(client_key.decrypt_integer(&scanner.count), 2);
+    /// assert_eq!(client_key.decrypt_integer(&scanner.first_match), 3);
+    /// ```
+    pub fn new_scanner(&self, pattern: &str) -> FheStringScanner {
+        let zero = self.create_scanner_zero();
+        FheStringScanner {
+            pattern: pattern.to_string(),
+            tail: vec![FheAsciiChar(self.create_zero()); pattern.len().saturating_sub(1)],
+            consumed: zero.clone(),
+            // The empty pattern matches at the start of the stream.
+            contains: self.create_bool(pattern.is_empty()),
+            first_match: zero.clone(),
+            count: self
+                .integer_key
+                .scalar_add_parallelized(&zero, pattern.is_empty() as u64),
+            next_allowed_offset: zero,
+        }
+    }
+
+    /// Consumes a chunk of the stream and updates the encrypted state of the scanner.
+    pub fn scan(&self, scanner: &mut FheStringScanner, chunk: &FheString) {
+        let final_padding_chunk;
+        let chunk = match chunk.padding {
+            Padding::None | Padding::Final => chunk,
+            _ => {
+                final_padding_chunk = self.push_padding_to_end(chunk);
+                &final_padding_chunk
+            }
+        };
+        let chunk_length = self.integer_key.extend_radix_with_trivial_zero_blocks_msb(
+            &self.add_length_to_radix(&self.create_zero(), &chunk.length),
+            SCANNER_NUMBER_BLOCKS - NUMBER_BLOCKS,
+        );
+
+        if scanner.pattern.is_empty() {
+            // The empty pattern matches after each character.
+            self.integer_key
+                .add_assign_parallelized(&mut scanner.count, &chunk_length);
+            self.integer_key
+                .add_assign_parallelized(&mut scanner.consumed, &chunk_length);
+            return;
+        }
+
+        let pattern = scanner.pattern.as_str();
+        let tail_length = scanner.tail.len();
+        let mut input = scanner.tail.clone();
+        input.extend(chunk.content.iter().cloned());
+
+        for n in 0..(input.len() + 1).saturating_sub(pattern.len()) {
+            let current_match = pattern.is_prefix_of_slice(self, &input[n..]);
+            // Offset of input[n] in the stream. It wraps around for the leading zeros of the tail,
+            // where there is no match.
+            let offset = self.integer_key.scalar_sub_parallelized(
+                &self
+                    .integer_key
+                    .scalar_add_parallelized(&scanner.consumed, n as u64),
+                tail_length as u64,
+            );
+
+            let is_first_match = self.integer_key.boolean_bitand(
+                &current_match,
+                &self.integer_key.boolean_bitnot(&scanner.contains),
+            );
+            scanner.first_match =
+                self.integer_key
+                    .cmux_parallelized(&is_first_match, &offset, &scanner.first_match);
+            self.integer_key
+                .boolean_bitor_assign(&mut scanner.contains, &current_match);
+
+            let counted_match = self.integer_key.boolean_bitand(
+                &current_match,
+                &self
+                    .integer_key
+                    .ge_parallelized(&offset, &scanner.next_allowed_offset),
+            );
+            self.integer_key.add_assign_parallelized(
+                &mut scanner.count,
+                &self.integer_key.extend_radix_with_trivial_zero_blocks_msb(
+                    &self.bool_to_radix(&counted_match),
+                    SCANNER_NUMBER_BLOCKS - NUMBER_BLOCKS,
+                ),
+            );
+            scanner.next_allowed_offset = self.integer_key.cmux_parallelized(
+                &counted_match,
+                &self
+                    .integer_key
+                    .scalar_add_parallelized(&offset, pattern.len() as u64),
+                &scanner.next_allowed_offset,
+            );
+        }
+
+        // The new tail is made of the characters of input from index chunk length.
+        scanner.tail = match &chunk.length {
+            FheStrLength::Clear(length) => input[*length..*length + tail_length].to_vec(),
+            FheStrLength::Encrypted(length) => (0..tail_length)
+                .map(|j| {
+                    let mut c = self.create_zero();
+                    for k in 0..=chunk.content.len() {
+                        c = self.integer_key.cmux_parallelized(
+                            &self.integer_key.scalar_eq_parallelized(length, k as u64),
+                            &input[k + j].0,
+                            &c,
+                        );
+                    }
+                    FheAsciiChar(c)
+                })
+                .collect(),
+        };
+        self.integer_key
+            .add_assign_parallelized(&mut scanner.consumed, &chunk_length);
+    }
+
+    fn create_scanner_zero(&self) -> RadixCiphertext {
+        self.integer_key
+            .create_trivial_zero_radix(SCANNER_NUMBER_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_scanner(chunks: &[&str], padding: usize, pattern: &str) {
+        let mut scanner = SERVER_KEY.new_scanner(pattern);
+        for chunk in chunks {
+            let encrypted_chunk = CLIENT_KEY
+                .encrypt_str_random_padding(chunk, padding)
+                .unwrap();
+            SERVER_KEY.scan(&mut scanner, &encrypted_chunk);
+        }
+        let stream = chunks.concat();
+        let contains = CLIENT_KEY.decrypt_bool(&scanner.contains);
+        assert_eq!(contains, stream.contains(pattern));
+        if let Some(offset) = stream.find(pattern) {
+            assert_eq!(
+                CLIENT_KEY.decrypt_integer(&scanner.first_match) as usize,
+                offset
+            );
+        }
+        assert_eq!(
+            CLIENT_KEY.decrypt_integer(&scanner.count) as usize,
+            stream.matches(pattern).count()
+        );
+        assert_eq!(
+            CLIENT_KEY.decrypt_integer(&scanner.consumed) as usize,
+            stream.len()
+        );
+    }
+
+    #[test]
+    fn test_scanner() {
+        check_scanner(&["xab", "ab", "a", "b"], 0, "ab");
+        check_scanner(&["aa", "aa", "a"], 1, "aa");
+        check_scanner(&["ab", "c"], 2, "abc");
+        check_scanner(&["ab", "", "cab"], 1, "bca");
+        check_scanner(&["ab", "c"], 1, "");
+        check_scanner(&["a", "b"], 1, "x");
+    }
+}
@@ -0,0 +1,265 @@
+use crate::ciphertext::{FheAsciiChar, FheStrLength, FheString, Padding};
+use crate::server_key::StringServerKey;
+use std::collections::HashSet;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// FHE version of a fraction (numerator, denominator), as returned by the Jaccard similarity.
+pub type FheRatio = (RadixCiphertext, RadixCiphertext);
+
+/// The n-grams of an encrypted string with final padding.
+struct FheNgrams<'a> {
+    content: &'a [FheAsciiChar],
+    n: usize,
+    /// For each index, an encryption of true if an n-gram of the string starts at this index and
+    /// no n-gram equal to it starts before.
+    is_first_occurrence: Vec<BooleanBlock>,
+}
+
+impl StringServerKey {
+    /// Returns the number of distinct n-grams (substrings of n characters) which are shared by s1
+    /// and s2. The padding of s1 and s2 is pushed to the end if needed.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str1 = client_key.encrypt_str("night").unwrap();
+    /// let encrypted_str2 = client_key.encrypt_str("nacht").unwrap();
+    /// let shared = server_key.ngram_similarity(&encrypted_str1, &encrypted_str2, 2);
+    /// assert_eq!(client_key.decrypt_integer(&shared), 1);
+    /// ```
+    pub fn ngram_similarity(&self, s1: &FheString, s2: &FheString, n: usize) -> RadixCiphertext {
+        let (s1, s2) = (self.push_padding_to_end(s1), self.push_padding_to_end(s2));
+        let ngrams1 = self.distinct_ngrams(&s1, n);
+        self.shared_ngrams_encrypted(&ngrams1, &s2.content)
+    }
+
+    /// Returns the number of distinct n-grams (substrings of n characters) which are shared by s1
+    /// and the clear string s2. The padding of s1 is pushed to the end if needed.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("night").unwrap();
+    /// let shared = server_key.ngram_similarity_clear(&encrypted_str, "nigh", 2);
+    /// assert_eq!(client_key.decrypt_integer(&shared), 3);
+    /// ```
+    pub fn ngram_similarity_clear(&self, s1: &FheString, s2: &str, n: usize) -> RadixCiphertext {
+        let s1 = self.push_padding_to_end(s1);
+        let ngrams1 = self.distinct_ngrams(&s1, n);
+        self.shared_ngrams_clear(&ngrams1, &clear_distinct_ngrams(s2, n))
+    }
+
+    /// Returns the Jaccard similarity between the sets of n-grams of s1 and s2, as a pair
+    /// (numerator, denominator) of the number of shared n-grams and the number of distinct n-grams
+    /// in s1 or s2. Both are encrypted on the same number of bits as lengths, so the total number
+    /// of characters of s1 and s2 should not exceed 255. The padding of s1 and s2 is pushed to the
+    /// end if needed.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str1 = client_key.encrypt_str("night").unwrap();
+    /// let encrypted_str2 = client_key.encrypt_str("nacht").unwrap();
+    /// let (numerator, denominator) = server_key.jaccard(&encrypted_str1, &encrypted_str2, 2);
+    /// assert_eq!(client_key.decrypt_integer(&numerator), 1);
+    /// assert_eq!(client_key.decrypt_integer(&denominator), 7);
+    /// ```
+    pub fn jaccard(&self, s1: &FheString, s2: &FheString, n: usize) -> FheRatio {
+        let (s1, s2) = (self.push_padding_to_end(s1), self.push_padding_to_end(s2));
+        let ngrams1 = self.distinct_ngrams(&s1, n);
+        let ngrams2 = self.distinct_ngrams(&s2, n);
+        let shared = self.shared_ngrams_encrypted(&ngrams1, &s2.content);
+        let union = self.integer_key.sub_parallelized(
+            &self.integer_key.add_parallelized(
+                &self.count_distinct_ngrams(&ngrams1),
+                &self.count_distinct_ngrams(&ngrams2),
+            ),
+            &shared,
+        );
+        (shared, union)
+    }
+
+    /// Returns the Jaccard similarity between the sets of n-grams of s1 and of the clear string
+    /// s2, as a pair (numerator, denominator). See `jaccard`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("night").unwrap();
+    /// let (numerator, denominator) = server_key.jaccard_clear(&encrypted_str, "nigh", 2);
+    /// assert_eq!(client_key.decrypt_integer(&numerator), 3);
+    /// assert_eq!(client_key.decrypt_integer(&denominator), 4);
+    /// ```
+    pub fn jaccard_clear(&self, s1: &FheString, s2: &str, n: usize) -> FheRatio {
+        let s1 = self.push_padding_to_end(s1);
+        let ngrams1 = self.distinct_ngrams(&s1, n);
+        let ngrams2 = clear_distinct_ngrams(s2, n);
+        let shared = self.shared_ngrams_clear(&ngrams1, &ngrams2);
+        let union = self.integer_key.sub_parallelized(
+            &self.integer_key.scalar_add_parallelized(
+                &self.count_distinct_ngrams(&ngrams1),
+                ngrams2.len() as u64,
+            ),
+            &shared,
+        );
+        (shared, union)
+    }
+
+    /// Computes, for each n-gram of s, whether it is the first occurrence of its value. The
+    /// padding of s should be final.
+    fn distinct_ngrams<'a>(&self, s: &'a FheString, n: usize) -> FheNgrams<'a> {
+        assert!(n > 0, "n-grams should not be empty");
+        let content = &s.content[..];
+        let number_ngrams = (content.len() + 1).saturating_sub(n);
+        let mut is_first_occurrence = Vec::with_capacity(number_ngrams);
+        for i in 0..number_ngrams {
+            // With final padding, the n-gram is in s if its last character is not a padding zero.
+            let mut is_first = self.ne_clear_char(&content[i + n - 1], 0);
+            let ngram = unpadded_ngram(content, i, n);
+            for j in 0..i {
+                let is_repeated = self.starts_with_encrypted_vec(&content[j..j + n], &ngram);
+                self.integer_key.boolean_bitand_assign(
+                    &mut is_first,
+                    &self.integer_key.boolean_bitnot(&is_repeated),
+                );
+            }
+            is_first_occurrence.push(is_first);
+        }
+        FheNgrams {
+            content,
+            n,
+            is_first_occurrence,
+        }
+    }
+
+    fn count_distinct_ngrams(&self, ngrams: &FheNgrams) -> RadixCiphertext {
+        let mut count = self.create_zero();
+        for is_first in &ngrams.is_first_occurrence {
+            self.integer_key
+                .add_assign_parallelized(&mut count, &self.bool_to_radix(is_first));
+        }
+        count
+    }
+
+    /// Counts the distinct n-grams of `ngrams` which are also n-grams of `content`, whose padding
+    /// should be final.
+    fn shared_ngrams_encrypted(
+        &self,
+        ngrams: &FheNgrams,
+        content: &[FheAsciiChar],
+    ) -> RadixCiphertext {
+        let n = ngrams.n;
+        let mut count = self.create_zero();
+        for (i, is_first) in ngrams.is_first_occurrence.iter().enumerate() {
+            let ngram = unpadded_ngram(ngrams.content, i, n);
+            let mut is_shared = self.create_false();
+            for j in 0..(content.len() + 1).saturating_sub(n) {
+                // The n-gram of s1 is not made of padding zeros, so an equal n-gram is not either.
+                self.integer_key.boolean_bitor_assign(
+                    &mut is_shared,
+                    &self.starts_with_encrypted_vec(&content[j..j + n], &ngram),
+                );
+            }
+            self.integer_key.add_assign_parallelized(
+                &mut count,
+                &self.bool_to_radix(&self.integer_key.boolean_bitand(is_first, &is_shared)),
+            );
+        }
+        count
+    }
+
+    /// Counts the distinct n-grams of `ngrams` which are in the clear set `clear_ngrams`.
+    fn shared_ngrams_clear(
+        &self,
+        ngrams: &FheNgrams,
+        clear_ngrams: &HashSet<&[u8]>,
+    ) -> RadixCiphertext {
+        let n = ngrams.n;
+        let mut count = self.create_zero();
+        for (i, is_first) in ngrams.is_first_occurrence.iter().enumerate() {
+            let mut is_shared = self.create_false();
+            for clear_ngram in clear_ngrams {
+                let is_equal = clear_ngram
+                    .iter()
+                    .zip(&ngrams.content[i..i + n])
+                    .map(|(clear_char, c)| self.eq_clear_char(c, *clear_char))
+                    .reduce(|a, b| self.integer_key.boolean_bitand(&a, &b))
+                    .unwrap();
+                self.integer_key
+                    .boolean_bitor_assign(&mut is_shared, &is_equal);
+            }
+            self.integer_key.add_assign_parallelized(
+                &mut count,
+                &self.bool_to_radix(&self.integer_key.boolean_bitand(is_first, &is_shared)),
+            );
+        }
+        count
+    }
+}
+
+/// The n characters of `content` starting at index i, as an FheString without padding.
+fn unpadded_ngram(content: &[FheAsciiChar], i: usize, n: usize) -> FheString {
+    FheString {
+        content: content[i..i + n].to_vec(),
+        padding: Padding::None,
+        length: FheStrLength::Clear(n),
+    }
+}
+
+fn clear_distinct_ngrams(s: &str, n: usize) -> HashSet<&[u8]> {
+    assert!(n > 0, "n-grams should not be empty");
+    s.as_bytes().windows(n).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+    use std::collections::HashSet;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn jaccard(s1: &str, s2: &str, n: usize) -> (u32, u32) {
+        let ngrams1: HashSet<&[u8]> = s1.as_bytes().windows(n).collect();
+        let ngrams2: HashSet<&[u8]> = s2.as_bytes().windows(n).collect();
+        (
+            ngrams1.intersection(&ngrams2).count() as u32,
+            ngrams1.union(&ngrams2).count() as u32,
+        )
+    }
+
+    fn check_similarity(s1: &str, padding1: usize, s2: &str, padding2: usize, n: usize) {
+        let encrypted_s1 = CLIENT_KEY.encrypt_str_random_padding(s1, padding1).unwrap();
+        let encrypted_s2 = CLIENT_KEY.encrypt_str_random_padding(s2, padding2).unwrap();
+        let expected = jaccard(s1, s2, n);
+        let decrypt_ratio = |(numerator, denominator)| {
+            (
+                CLIENT_KEY.decrypt_integer(&numerator),
+                CLIENT_KEY.decrypt_integer(&denominator),
+            )
+        };
+
+        let shared = SERVER_KEY.ngram_similarity(&encrypted_s1, &encrypted_s2, n);
+        assert_eq!(CLIENT_KEY.decrypt_integer(&shared), expected.0);
+        let shared = SERVER_KEY.ngram_similarity_clear(&encrypted_s1, s2, n);
+        assert_eq!(CLIENT_KEY.decrypt_integer(&shared), expected.0);
+        let ratio = SERVER_KEY.jaccard(&encrypted_s1, &encrypted_s2, n);
+        assert_eq!(decrypt_ratio(ratio), expected);
+        let ratio = SERVER_KEY.jaccard_clear(&encrypted_s1, s2, n);
+        assert_eq!(decrypt_ratio(ratio), expected);
+    }
+
+    #[test]
+    fn test_jaccard() {
+        check_similarity("abab", 1, "babb", 2, 2);
+        check_similarity("aaa", 0, "aa", 1, 2);
+        check_similarity("ab", 2, "abc", 0, 3);
+        check_similarity("", 1, "a", 0, 1);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::ciphertext::{ClearOrEncrypted, FheStrLength, FheString, Padding};
 use crate::integer_arg::FheIntegerArg;
 use crate::pattern::{FheCharPattern, FhePattern};
+use crate::server_key::find::FheOptionInt;
 use crate::server_key::StringServerKey;
+use serde::{Deserialize, Serialize};
 use tfhe::integer::{BooleanBlock, RadixCiphertext};
 
 pub type ResultFheString = (BooleanBlock, FheString);
@@ -10,6 +12,7 @@ pub type ResultFheString = (BooleanBlock, FheString);
 /// splitting are stored as the first number_parts encrypted string in the vector parts. Number of
 /// parts is an encrypted integer. The elements in parts after number_parts are not assured to be
 /// empty string and should be ignored.
+#[derive(Serialize, Deserialize)]
 pub struct FheSplit {
     pub parts: Vec<FheString>,
     pub number_parts: RadixCiphertext,
@@ -283,9 +286,9 @@ impl StringServerKey {
                 content: vec![s.content[i].clone()],
                 length: match s.len() {
                     FheStrLength::Clear(_) => FheStrLength::Clear(1),
-                    _ => FheStrLength::Encrypted(self.bool_to_radix(
-                        &self.integer_key.scalar_ne_parallelized(&s.content[i].0, 0),
-                    )),
+                    _ => FheStrLength::Encrypted(
+                        self.bool_to_radix(&self.ne_clear_char(&s.content[i], 0)),
+                    ),
                 },
                 padding: match s.len() {
                     FheStrLength::Clear(_) => Padding::None,
@@ -331,7 +334,7 @@ impl StringServerKey {
         };
         parts.push(empty_string.clone());
         for c in &s.content {
-            let current_char_non_null = self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let current_char_non_null = self.ne_clear_char(c, 0);
             parts.push(FheString {
                 padding: Padding::Final,
                 length: ClearOrEncrypted::Encrypted(self.bool_to_radix(&current_char_non_null)),
@@ -381,38 +384,29 @@ impl StringServerKey {
     }
 
     pub fn split_clear_final_padding(&self, s: &FheString, pattern: &str) -> FheSplit {
-        // Compute the maximum number of parts of the result.
-        let maximum_number_of_parts = match &s.length {
-            ClearOrEncrypted::Clear(length) => *length + 2,
-            _ => s.content.len() + 2,
-        };
-        let mut parts: Vec<FheString> = Vec::with_capacity(maximum_number_of_parts);
-        let zero = self.create_zero();
-        let mut number_parts = self.create_n(1); // The result has at least 1 part.
-
-        // `start_part` holds the index of the beginning of the current part.
-        let mut start_part = zero.clone();
-
-        for _ in 0..maximum_number_of_parts {
-            let (found, end_part) = self.find_clear_from_final_padding(s, pattern, &start_part);
-
-            // Increment `number_parts` if the pattern is found.
-            self.integer_key
-                .add_assign_parallelized(&mut number_parts, &self.bool_to_radix(&found));
-
-            parts.push(self.substring_encrypted_final_padding(s, &start_part, &end_part));
-            start_part = self
-                .integer_key
-                .scalar_add_parallelized(&end_part, pattern.len() as u64);
-        }
-        FheSplit {
-            parts,
-            number_parts,
-            current_index: 0,
-        }
+        self.split_final_padding_with_find(s, &FheStrLength::Clear(pattern.len()), |from| {
+            self.find_clear_from_final_padding(s, pattern, from)
+        })
     }
 
     pub fn split_encrypted_final_padding(&self, s: &FheString, pattern: &FheString) -> FheSplit {
+        self.split_final_padding_with_find(s, &pattern.length, |from| {
+            self.find_from_final_padding(s, pattern, from)
+        })
+    }
+
+    /// Splits `s`, which has at worst final padding, at the occurences of a non empty pattern of
+    /// length `pattern_length`. `find_from` returns the first occurence starting at or after the
+    /// given index.
+    pub fn split_final_padding_with_find<F>(
+        &self,
+        s: &FheString,
+        pattern_length: &FheStrLength,
+        find_from: F,
+    ) -> FheSplit
+    where
+        F: Fn(&RadixCiphertext) -> FheOptionInt,
+    {
         // Compute the maximum number of parts of the result.
         let maximum_number_of_parts = match &s.length {
             ClearOrEncrypted::Clear(length) => *length + 2,
@@ -426,14 +420,14 @@ impl StringServerKey {
         let mut start_part = zero.clone();
 
         for _ in 0..maximum_number_of_parts {
-            let (found, end_part) = self.find_from_final_padding(s, pattern, &start_part);
+            let (found, end_part) = find_from(&start_part);
 
             // Increment `number_parts` if the pattern is found.
             self.integer_key
                 .add_assign_parallelized(&mut number_parts, &self.bool_to_radix(&found));
 
             parts.push(self.substring_encrypted_final_padding(s, &start_part, &end_part));
-            start_part = self.add_length_to_radix(&end_part, &pattern.length);
+            start_part = self.add_length_to_radix(&end_part, pattern_length);
         }
         FheSplit {
             parts,
@@ -493,6 +487,23 @@ impl StringServerKey {
         s: &FheString,
         pattern: &FheString,
     ) -> FheSplit {
+        self.split_encrypted_final_padding_allow_empty_pattern_with_find(s, pattern, |from| {
+            self.find_from_final_padding_allow_empty_pattern(s, pattern, from)
+        })
+    }
+
+    /// Same as `split_encrypted_final_padding_allow_empty_pattern` but the occurences of `pattern`
+    /// are found by `find_from`, which returns the first occurence starting at or after the given
+    /// index.
+    pub fn split_encrypted_final_padding_allow_empty_pattern_with_find<F>(
+        &self,
+        s: &FheString,
+        pattern: &FheString,
+        find_from: F,
+    ) -> FheSplit
+    where
+        F: Fn(&RadixCiphertext) -> FheOptionInt,
+    {
         // Compute the maximum number of parts of the result.
         let maximum_number_of_parts = match &s.length {
             ClearOrEncrypted::Clear(length) => *length + 2,
@@ -511,16 +522,13 @@ impl StringServerKey {
             let end_part: RadixCiphertext;
             if n >= 1 {
                 // When the patern is empty, the search must start at `start_part` plus 1.
-                (found, end_part) = self.find_from_final_padding_allow_empty_pattern(
-                    s,
-                    pattern,
+                (found, end_part) = find_from(
                     &self
                         .integer_key
                         .add_parallelized(&start_part, &self.bool_to_radix(&empty_pattern)),
                 );
             } else {
-                (found, end_part) =
-                    self.find_from_final_padding_allow_empty_pattern(s, pattern, &start_part);
+                (found, end_part) = find_from(&start_part);
             }
 
             // Increment `number_parts` if the pattern is found.
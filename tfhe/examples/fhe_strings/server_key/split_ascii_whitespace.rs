@@ -61,21 +61,27 @@ impl StringServerKey {
     }
 
     pub fn is_ascii_white_space(&self, c: &FheAsciiChar) -> BooleanBlock {
+        if let Some(clear_c) = c.clear_value() {
+            return self.create_bool(matches!(clear_c, 9..=13 | 32));
+        }
         let is_tab_feed_return = self.integer_key.boolean_bitand(
             &self.integer_key.scalar_ge_parallelized(&c.0, 9),
             &self.integer_key.scalar_le_parallelized(&c.0, 13),
         );
-        let is_space = self.integer_key.scalar_eq_parallelized(&c.0, 32);
+        let is_space = self.eq_clear_char(c, 32);
         self.integer_key
             .boolean_bitor(&is_tab_feed_return, &is_space)
     }
 
     pub fn is_not_ascii_white_space(&self, c: &FheAsciiChar) -> BooleanBlock {
+        if let Some(clear_c) = c.clear_value() {
+            return self.create_bool(!matches!(clear_c, 9..=13 | 32));
+        }
         let is_not_tab_feed_return = self.integer_key.boolean_bitor(
             &self.integer_key.scalar_lt_parallelized(&c.0, 9),
             &self.integer_key.scalar_gt_parallelized(&c.0, 13),
         );
-        let is_not_space = self.integer_key.scalar_ne_parallelized(&c.0, 32);
+        let is_not_space = self.ne_clear_char(c, 32);
         self.integer_key
             .boolean_bitand(&is_not_tab_feed_return, &is_not_space)
     }
@@ -90,7 +96,7 @@ impl StringServerKey {
         for (i, c) in s.content.iter().enumerate() {
             let in_range = self.integer_key.scalar_le_parallelized(from, i as u32);
             let non_white = self.is_not_ascii_white_space(c);
-            let non_null = self.integer_key.scalar_ne_parallelized(&c.0, 0);
+            let non_null = self.ne_clear_char(c, 0);
             let content_in_range = self.integer_key.boolean_bitand(
                 &self.integer_key.boolean_bitand(&in_range, &non_white),
                 &non_null,
@@ -149,10 +155,9 @@ impl StringServerKey {
             let in_range = self.integer_key.scalar_le_parallelized(from, i as u32);
             let non_white = self.is_not_ascii_white_space(c);
             let non_white_and_in_range = self.integer_key.boolean_bitand(&non_white, &in_range);
-            let is_content_in_range = self.integer_key.boolean_bitand(
-                &non_white_and_in_range,
-                &self.integer_key.scalar_ne_parallelized(&c.0, 0),
-            );
+            let is_content_in_range = self
+                .integer_key
+                .boolean_bitand(&non_white_and_in_range, &self.ne_clear_char(c, 0));
 
             let first_content_in_range = self
                 .integer_key
@@ -201,7 +206,7 @@ impl StringServerKey {
         start: &RadixCiphertext,
         end: &RadixCiphertext,
     ) -> FheString {
-        let zero = &self.create_zero();
+        let zero = &FheAsciiChar(self.create_zero());
         let mut result_content: Vec<FheAsciiChar> = Vec::with_capacity(s.content.len());
         for (n, c) in s.content.iter().enumerate() {
             // Check if the index `n` is in the range `start`-`end`.
@@ -211,9 +216,7 @@ impl StringServerKey {
             );
 
             // If `n` is in range, take the content of `s` otherwise take a null character.
-            let new_char_content: RadixCiphertext =
-                self.integer_key.cmux_parallelized(&in_range, &c.0, zero);
-            result_content.push(FheAsciiChar(new_char_content));
+            result_content.push(self.cmux_char(&in_range, c, zero));
         }
         let result_padding = match s.padding {
             Padding::Anywhere => Padding::Anywhere,
@@ -235,7 +238,7 @@ impl StringServerKey {
         for c in content {
             self.integer_key.add_assign_parallelized(
                 &mut result,
-                &self.bool_to_radix(&self.integer_key.scalar_ne_parallelized(&c.0, 0)),
+                &self.bool_to_radix(&self.ne_clear_char(c, 0)),
             )
         }
         FheStrLength::Encrypted(result)
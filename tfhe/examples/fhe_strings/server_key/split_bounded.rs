@@ -0,0 +1,250 @@
+use crate::ciphertext::FheString;
+use crate::integer_arg::FheIntegerArg;
+use crate::pattern::FhePattern;
+use crate::server_key::split::FheSplit;
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+/// Result of a split with a bounded number of parts. An encryption of (overflowed, split) where
+/// overflowed is true if the unbounded split has more parts than the capacity, in which case
+/// `split` holds its first parts only.
+pub type FheBoundedSplit = (BooleanBlock, FheSplit);
+
+impl StringServerKey {
+    /// Splits the string s at each occurence of pattern, keeping at most `max_parts` parts, with
+    /// `max_parts` at most 255. The parts vector of the result has `max_parts` elements, instead
+    /// of one element per character of s for `split`, so the cost is
+    /// O(s.content.len() * (pattern.content.len() + max_parts)). Returns an encryption of true
+    /// with the first `max_parts` parts if s has more parts.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a,b,c").unwrap();
+    /// let (overflowed, fhe_split) = server_key.split_bounded(&encrypted_str, &',', 2);
+    /// let clear_overflowed = client_key.decrypt_integer(&server_key.bool_to_radix(&overflowed));
+    /// assert_eq!(clear_overflowed, 1);
+    /// assert_eq!(client_key.decrypt_integer(&fhe_split.number_parts), 2);
+    /// assert_eq!(client_key.decrypt_string(&fhe_split.parts[1]).unwrap(), "b");
+    /// ```
+    pub fn split_bounded(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        max_parts: usize,
+    ) -> FheBoundedSplit {
+        self.split_bounded_final_padding(&self.push_padding_to_end(s), pattern, None, max_parts)
+    }
+
+    /// Splits the string s at each occurence of pattern at most n - 1 times, like `splitn`,
+    /// keeping at most `max_parts` parts. See `split_bounded`. Returns an encryption of true with
+    /// the first `max_parts` parts if `splitn` returns more parts.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("a,b,c").unwrap();
+    /// let n = client_key.encrypt_integer(2u32);
+    /// let (overflowed, fhe_split) = server_key.splitn_bounded(&encrypted_str, &n, &',', 2);
+    /// let clear_overflowed = client_key.decrypt_integer(&server_key.bool_to_radix(&overflowed));
+    /// assert_eq!(clear_overflowed, 0);
+    /// assert_eq!(client_key.decrypt_string(&fhe_split.parts[1]).unwrap(), "b,c");
+    /// ```
+    pub fn splitn_bounded(
+        &self,
+        s: &FheString,
+        n: &impl FheIntegerArg,
+        pattern: &impl FhePattern,
+        max_parts: usize,
+    ) -> FheBoundedSplit {
+        self.split_bounded_final_padding(
+            &self.push_padding_to_end(s),
+            pattern,
+            Some(&n.to_radix(self)),
+            max_parts,
+        )
+    }
+
+    /// Splits s, which has at worst final padding, at each occurence of pattern, and at most
+    /// n - 1 times if n is given. The k-th part ends at the k-th non overlapping occurence of
+    /// pattern, except for the last part which ends at the end of s. The pattern is compared once
+    /// at each position of s, and the cuts are derived from these matches.
+    fn split_bounded_final_padding(
+        &self,
+        s: &FheString,
+        pattern: &impl FhePattern,
+        n: Option<&RadixCiphertext>,
+        max_parts: usize,
+    ) -> FheBoundedSplit {
+        assert!(max_parts > 0, "the split should have at least one part");
+        // The number of parts is encrypted on the same number of bits as lengths.
+        assert!(
+            max_parts <= u8::MAX as usize,
+            "the split should have at most 255 parts"
+        );
+        let zero = self.create_zero();
+        let length = self.add_length_to_radix(&zero, &s.length);
+        let pattern_length = pattern.length_radix(self);
+        // Occurences of the empty pattern are one character apart.
+        let step = self.integer_key.scalar_max_parallelized(&pattern_length, 1);
+        let mut is_cut = vec![self.create_false(); max_parts];
+        let mut cut_index = vec![length.clone(); max_parts];
+        let mut count = zero.clone();
+        // The first position where the next occurence can start.
+        let mut limit = zero.clone();
+
+        for (position, current_match) in pattern.prefix_matches_in(self, s, 0).iter().enumerate() {
+            let mut current_match = self.integer_key.boolean_bitand(
+                current_match,
+                &self
+                    .integer_key
+                    .scalar_le_parallelized(&limit, position as u64),
+            );
+            // The empty pattern matches in the final padding, positions after the end are masked.
+            self.integer_key.boolean_bitand_assign(
+                &mut current_match,
+                &self
+                    .integer_key
+                    .scalar_ge_parallelized(&length, position as u64),
+            );
+            for (k, (is_cut, cut_index)) in is_cut.iter_mut().zip(&mut cut_index).enumerate() {
+                let is_kth = self.integer_key.boolean_bitand(
+                    &current_match,
+                    &self.integer_key.scalar_eq_parallelized(&count, k as u64),
+                );
+                *cut_index = self.integer_key.cmux_parallelized(
+                    &is_kth,
+                    &self.create_n(position as u8),
+                    cut_index,
+                );
+                self.integer_key.boolean_bitor_assign(is_cut, &is_kth);
+            }
+            self.integer_key
+                .add_assign_parallelized(&mut count, &self.bool_to_radix(&current_match));
+            limit = self.integer_key.cmux_parallelized(
+                &current_match,
+                &self
+                    .integer_key
+                    .scalar_add_parallelized(&step, position as u64),
+                &limit,
+            );
+        }
+
+        let mut parts = Vec::with_capacity(max_parts);
+        let mut number_parts = self.create_n(1);
+        let mut overflowed = self.create_false();
+        let mut start_part = zero;
+        for (k, (mut is_cut, mut end_part)) in is_cut.into_iter().zip(cut_index).enumerate() {
+            if let Some(n) = n {
+                self.integer_key.boolean_bitand_assign(
+                    &mut is_cut,
+                    &self.integer_key.scalar_gt_parallelized(n, k as u64 + 1),
+                );
+                end_part = self
+                    .integer_key
+                    .cmux_parallelized(&is_cut, &end_part, &length);
+            }
+            parts.push(self.substring_encrypted_final_padding(s, &start_part, &end_part));
+            // A cut after the last kept part means that some parts do not fit.
+            match k + 1 < max_parts {
+                true => self
+                    .integer_key
+                    .add_assign_parallelized(&mut number_parts, &self.bool_to_radix(&is_cut)),
+                false => overflowed = is_cut,
+            }
+            start_part = self
+                .integer_key
+                .add_parallelized(&end_part, &pattern_length);
+        }
+        if let Some(n) = n {
+            // splitn returns no part for n = 0.
+            number_parts = self.integer_key.min_parallelized(&number_parts, n);
+        }
+        (
+            overflowed,
+            FheSplit {
+                parts,
+                number_parts,
+                current_index: 0,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::split_bounded::FheBoundedSplit;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn decrypt_bounded_split(result: &FheBoundedSplit) -> (bool, Vec<String>) {
+        let (overflowed, fhe_split) = result;
+        let number_parts = CLIENT_KEY.decrypt_integer(&fhe_split.number_parts) as usize;
+        let parts = fhe_split.parts[..number_parts]
+            .iter()
+            .map(|part| CLIENT_KEY.decrypt_string(part).unwrap())
+            .collect();
+        (CLIENT_KEY.decrypt_bool(overflowed), parts)
+    }
+
+    fn expected_bounded_split<'a>(
+        parts: impl Iterator<Item = &'a str>,
+        max_parts: usize,
+    ) -> (bool, Vec<String>) {
+        let parts: Vec<String> = parts.map(String::from).collect();
+        (
+            parts.len() > max_parts,
+            parts.into_iter().take(max_parts).collect(),
+        )
+    }
+
+    fn check_split_bounded(s: &str, padding: usize, pattern: &str, max_parts: usize) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let encrypted_pattern = CLIENT_KEY
+            .encrypt_str_random_padding(pattern, padding)
+            .unwrap();
+        let expected = expected_bounded_split(s.split(pattern), max_parts);
+
+        let result = SERVER_KEY.split_bounded(&encrypted_s, &pattern, max_parts);
+        assert_eq!(decrypt_bounded_split(&result), expected);
+        let result = SERVER_KEY.split_bounded(&encrypted_s, &encrypted_pattern, max_parts);
+        assert_eq!(decrypt_bounded_split(&result), expected);
+    }
+
+    fn check_splitn_bounded(s: &str, n: usize, pattern: char, max_parts: usize) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, 1).unwrap();
+        let encrypted_n = CLIENT_KEY.encrypt_integer(n as u32);
+        let expected = expected_bounded_split(s.splitn(n, pattern), max_parts);
+
+        let result = SERVER_KEY.splitn_bounded(&encrypted_s, &n, &pattern, max_parts);
+        assert_eq!(decrypt_bounded_split(&result), expected);
+        let result = SERVER_KEY.splitn_bounded(&encrypted_s, &encrypted_n, &pattern, max_parts);
+        assert_eq!(decrypt_bounded_split(&result), expected);
+    }
+
+    #[test]
+    fn test_split_bounded() {
+        check_split_bounded("a,b,,c", 1, ",", 3);
+        check_split_bounded("a,b,,c", 0, ",", 5);
+        check_split_bounded("aaa", 2, "aa", 1);
+        check_split_bounded("ab", 1, "", 3);
+        check_split_bounded("ab", 0, "", 4);
+        check_split_bounded("", 1, "a", 1);
+    }
+
+    #[test]
+    fn test_splitn_bounded() {
+        check_splitn_bounded("a,b,c", 2, ',', 2);
+        check_splitn_bounded("a,b,c", 4, ',', 2);
+        check_splitn_bounded("a,b,c", 0, ',', 1);
+        check_splitn_bounded(",", 3, ',', 3);
+    }
+}
@@ -35,9 +35,7 @@ impl StringServerKey {
                             &prefix.content[n],
                             std::cmp::Ordering::Equal,
                         ),
-                        &self
-                            .integer_key
-                            .scalar_eq_parallelized(&prefix.content[n].0, 0),
+                        &self.eq_clear_char(&prefix.content[n], 0),
                     ),
                 },
             )
@@ -48,9 +46,7 @@ impl StringServerKey {
         if prefix.content.len() > s.content.len() {
             return self.integer_key.boolean_bitand(
                 &result,
-                &self
-                    .integer_key
-                    .scalar_eq_parallelized(&prefix.content[s.content.len()].0, 0),
+                &self.eq_clear_char(&prefix.content[s.content.len()], 0),
             );
         }
         result
@@ -69,10 +69,9 @@ impl StringServerKey {
         prefix: &impl FheCharPattern,
     ) -> FheOptionString {
         let is_prefix = prefix.fhe_eq(self, &s.content[0]);
-        let radix_first_char =
-            self.integer_key
-                .cmux_parallelized(&is_prefix, &self.create_zero(), &s.content[0].0);
-        let mut result_content = vec![FheAsciiChar(radix_first_char)];
+        let first_char =
+            self.cmux_char(&is_prefix, &FheAsciiChar(self.create_zero()), &s.content[0]);
+        let mut result_content = vec![first_char];
         result_content.extend_from_slice(&s.content[1..]);
         let result_length = self.sub_radix_to_length(s.len(), &self.bool_to_radix(&is_prefix));
         let result_padding = match s.padding {
@@ -140,16 +139,12 @@ impl StringServerKey {
             return (self.create_false(), s.clone());
         }
 
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
         let is_prefix = prefix.is_prefix_of_string(self, s);
         let mut result_content: Vec<FheAsciiChar> = vec![];
 
         for (i, _c) in prefix.bytes().enumerate() {
-            result_content.push(FheAsciiChar(self.integer_key.cmux_parallelized(
-                &is_prefix,
-                &zero,
-                &s.content[i].0,
-            )))
+            result_content.push(self.cmux_char(&is_prefix, &zero, &s.content[i]))
         }
 
         result_content.extend_from_slice(&s.content[prefix.len()..]);
@@ -273,13 +268,9 @@ impl StringServerKey {
         prefix_length: u8,
     ) -> Vec<FheAsciiChar> {
         let mut result: Vec<FheAsciiChar> = Vec::with_capacity(content.len());
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
         for n in 0..prefix_length {
-            result.push(FheAsciiChar(self.integer_key.unchecked_cmux(
-                starts_with_prefix,
-                &zero,
-                &content[n as usize].0,
-            )));
+            result.push(self.cmux_char(starts_with_prefix, &zero, &content[n as usize]));
         }
         result.extend_from_slice(&content[(prefix_length as usize)..]);
         result
@@ -294,7 +285,7 @@ impl StringServerKey {
     ) -> Vec<FheAsciiChar> {
         let mut result: Vec<FheAsciiChar> = Vec::with_capacity(content.len());
         let overlapping_content_length = std::cmp::min(content.len(), prefix_content.len());
-        let zero = self.create_zero();
+        let zero = FheAsciiChar(self.create_zero());
         for (n, c) in content.iter().enumerate().take(overlapping_content_length) {
             let erase = self.integer_key.boolean_bitand(
                 starts_with_prefix,
@@ -302,9 +293,7 @@ impl StringServerKey {
                     .integer_key
                     .scalar_ge_parallelized(prefix_length, (n + 1) as u8),
             );
-            result.push(FheAsciiChar(
-                self.integer_key.unchecked_cmux(&erase, &zero, &c.0),
-            ));
+            result.push(self.cmux_char(&erase, &zero, c));
         }
         result.extend_from_slice(&content[overlapping_content_length..]);
         result
@@ -120,7 +120,7 @@ impl StringServerKey {
         start: &RadixCiphertext,
         end: &RadixCiphertext,
     ) -> FheString {
-        let zero = &self.create_zero();
+        let zero = &FheAsciiChar(self.create_zero());
         let mut result_content: Vec<FheAsciiChar> = Vec::with_capacity(s.content.len());
         for (n, c) in s.content.iter().enumerate() {
             // Check if the index `n` is in the range `start`-`end`.
@@ -130,9 +130,7 @@ impl StringServerKey {
             );
 
             // If `n` is in range, take the content of `s` otherwise take a null character.
-            let new_char_content: RadixCiphertext =
-                self.integer_key.cmux_parallelized(&in_range, &c.0, zero);
-            result_content.push(FheAsciiChar(new_char_content));
+            result_content.push(self.cmux_char(&in_range, c, zero));
         }
         let result_padding = match s.padding {
             Padding::Anywhere => Padding::Anywhere,
@@ -155,16 +153,14 @@ impl StringServerKey {
         s: &FheString,
         start: &RadixCiphertext,
     ) -> FheString {
-        let zero = &self.create_zero();
+        let zero = &FheAsciiChar(self.create_zero());
         let mut result_content: Vec<FheAsciiChar> = Vec::with_capacity(s.content.len());
         for (n, c) in s.content.iter().enumerate() {
             // Check if the index `n` is in the range `start`-`end`.
             let in_range: BooleanBlock = self.integer_key.scalar_le_parallelized(start, n as u64);
 
             // If `n` is in range, take the content of `s` otherwise take a null character.
-            let new_char_content: RadixCiphertext =
-                self.integer_key.cmux_parallelized(&in_range, &c.0, zero);
-            result_content.push(FheAsciiChar(new_char_content));
+            result_content.push(self.cmux_char(&in_range, c, zero));
         }
 
         let padding_result = match s.padding {
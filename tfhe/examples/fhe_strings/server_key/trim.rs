@@ -48,6 +48,13 @@ impl StringServerKey {
             .scalar_add_parallelized(&self.integer_key.create_trivial_zero_radix(4), n)
     }
 
+    pub fn create_bool(&self, value: bool) -> BooleanBlock {
+        match value {
+            true => self.create_true(),
+            false => self.create_false(),
+        }
+    }
+
     pub fn server_encrypt_str(&self, s: &str) -> Result<FheString, ConversionError> {
         self.server_encrypt_ascii_vec(&s.as_bytes().to_vec())
     }
@@ -99,12 +106,8 @@ impl StringServerKey {
         character: &ClearOrEncryptedChar,
     ) -> BooleanBlock {
         match character {
-            ClearOrEncryptedChar::Clear(clear_char) => {
-                self.integer_key.scalar_eq_parallelized(&c.0, *clear_char)
-            }
-            ClearOrEncryptedChar::Encrypted(ref encrypted_char) => {
-                self.integer_key.eq_parallelized(&c.0, &encrypted_char.0)
-            }
+            ClearOrEncryptedChar::Clear(clear_char) => self.eq_clear_char(c, *clear_char),
+            ClearOrEncryptedChar::Encrypted(ref encrypted_char) => self.eq_char(c, encrypted_char),
         }
     }
 
@@ -185,11 +188,11 @@ impl StringServerKey {
             result_length =
                 self.sub_radix_to_length(&result_length, &self.bool_to_radix(&continue_triming));
 
-            result_content.push(FheAsciiChar(self.integer_key.cmux_parallelized(
+            result_content.push(self.cmux_char(
                 &continue_triming,
-                &self.create_zero(),
-                &c.0,
-            )))
+                &FheAsciiChar(self.create_zero()),
+                c,
+            ))
         }
 
         FheString {
@@ -210,11 +213,11 @@ impl StringServerKey {
             result_length =
                 self.sub_radix_to_length(&result_length, &self.bool_to_radix(&continue_triming));
 
-            result_content.push(FheAsciiChar(self.integer_key.cmux_parallelized(
+            result_content.push(self.cmux_char(
                 &continue_triming,
-                &self.integer_key.create_trivial_zero_radix(4),
-                &c.0,
-            )))
+                &FheAsciiChar(self.create_zero()),
+                c,
+            ))
         }
 
         FheString {
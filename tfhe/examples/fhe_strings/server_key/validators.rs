@@ -0,0 +1,424 @@
+use crate::ciphertext::{FheAsciiChar, FheString};
+use crate::server_key::StringServerKey;
+use std::collections::HashMap;
+use tfhe::integer::BooleanBlock;
+
+/// A class of clear characters, labelling the transitions of a `ClearAutomaton`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum CharClass {
+    Char(u8),
+    /// The characters in the range `low..=high`.
+    Range(u8, u8),
+}
+
+use CharClass::{Char, Range};
+
+const DIGIT: CharClass = Range(b'0', b'9');
+const LOWERCASE: CharClass = Range(b'a', b'z');
+const UPPERCASE: CharClass = Range(b'A', b'Z');
+const ALPHANUMERIC: [CharClass; 3] = [DIGIT, LOWERCASE, UPPERCASE];
+
+/// A deterministic automaton over clear characters. The state 0 is the initial state, and a
+/// missing transition leads to an implicit rejecting state.
+struct ClearAutomaton {
+    number_states: usize,
+    /// Transitions (from, classes, to), taken when the character is in one of the classes.
+    transitions: Vec<(usize, Vec<CharClass>, usize)>,
+    accepting_states: Vec<usize>,
+}
+
+impl ClearAutomaton {
+    fn new(number_states: usize, accepting_states: &[usize]) -> Self {
+        Self {
+            number_states,
+            transitions: Vec::new(),
+            accepting_states: accepting_states.to_vec(),
+        }
+    }
+
+    fn add_transition(&mut self, from: usize, classes: &[CharClass], to: usize) {
+        self.transitions.push((from, classes.to_vec(), to));
+    }
+
+    /// Automaton accepting the strings of the same length as `mask` where each `#` of the mask
+    /// matches a digit, each `?` any character, and each other character of the mask itself.
+    fn mask(mask: &str) -> Self {
+        let mut automaton = Self::new(mask.len() + 1, &[mask.len()]);
+        for (i, m) in mask.bytes().enumerate() {
+            match m {
+                b'#' => automaton.add_transition(i, &[DIGIT], i + 1),
+                b'?' => automaton.add_transition(i, &[Range(1, 127)], i + 1),
+                _ => automaton.add_transition(i, &[Char(m)], i + 1),
+            }
+        }
+        automaton
+    }
+
+    /// Automaton accepting the non empty strings of digits.
+    fn numeric() -> Self {
+        let mut automaton = Self::new(2, &[1]);
+        automaton.add_transition(0, &[DIGIT], 1);
+        automaton.add_transition(1, &[DIGIT], 1);
+        automaton
+    }
+
+    /// Automaton accepting the dates `YYYY-MM-DD` with a month in `01..=12` and a day in
+    /// `01..=31`.
+    fn iso_date() -> Self {
+        let mut automaton = Self::new(14, &[13]);
+        for i in 0..4 {
+            automaton.add_transition(i, &[DIGIT], i + 1);
+        }
+        automaton.add_transition(4, &[Char(b'-')], 5);
+        // Month.
+        automaton.add_transition(5, &[Char(b'0')], 6);
+        automaton.add_transition(5, &[Char(b'1')], 7);
+        automaton.add_transition(6, &[Range(b'1', b'9')], 8);
+        automaton.add_transition(7, &[Range(b'0', b'2')], 8);
+        automaton.add_transition(8, &[Char(b'-')], 9);
+        // Day.
+        automaton.add_transition(9, &[Char(b'0')], 10);
+        automaton.add_transition(9, &[Range(b'1', b'2')], 11);
+        automaton.add_transition(9, &[Char(b'3')], 12);
+        automaton.add_transition(10, &[Range(b'1', b'9')], 13);
+        automaton.add_transition(11, &[DIGIT], 13);
+        automaton.add_transition(12, &[Range(b'0', b'1')], 13);
+        automaton
+    }
+
+    /// Automaton accepting the IPv4 addresses in dotted decimal notation: four numbers in
+    /// `0..=255` without leading zeros, separated by dots.
+    fn ipv4() -> Self {
+        // States of the automaton reading one number, after: nothing, "0", "1", "1d", "2",
+        // "2[0-4]", "25", "[3-9]", and a complete number of two or three digits.
+        const STATES: usize = 9;
+        const ACCEPTING: [usize; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        let number_transitions = [
+            (0, Char(b'0'), 1),
+            (0, Char(b'1'), 2),
+            (0, Char(b'2'), 4),
+            (0, Range(b'3', b'9'), 7),
+            (2, DIGIT, 3),
+            (3, DIGIT, 8),
+            (4, Range(b'0', b'4'), 5),
+            (4, Char(b'5'), 6),
+            (4, Range(b'6', b'9'), 8),
+            (5, DIGIT, 8),
+            (6, Range(b'0', b'5'), 8),
+            (7, DIGIT, 8),
+        ];
+        let accepting_states: Vec<usize> = ACCEPTING.iter().map(|q| 3 * STATES + q).collect();
+        let mut automaton = Self::new(4 * STATES, &accepting_states);
+        for number in 0..4 {
+            let offset = number * STATES;
+            for (from, class, to) in number_transitions {
+                automaton.add_transition(offset + from, &[class], offset + to);
+            }
+            if number < 3 {
+                for q in ACCEPTING {
+                    automaton.add_transition(offset + q, &[Char(b'.')], offset + STATES);
+                }
+            }
+        }
+        automaton
+    }
+
+    /// Automaton accepting the email addresses `local@domain`, where the local part is made of
+    /// alphanumeric characters and `._%+-`, and the domain is made of at least two labels of
+    /// alphanumeric characters and `-` separated by dots, the last one having at least two letters.
+    fn email() -> Self {
+        let local_part: Vec<CharClass> = ALPHANUMERIC
+            .into_iter()
+            .chain(b"._%+-".iter().map(|c| Char(*c)))
+            .collect();
+        let letter = [LOWERCASE, UPPERCASE];
+        let digit_or_hyphen = [DIGIT, Char(b'-')];
+        let label = [DIGIT, LOWERCASE, UPPERCASE, Char(b'-')];
+        let dot = [Char(b'.')];
+        // States: start, local part, after '@', in a label, after a dot, after a dot and one
+        // letter, after a dot and at least two letters.
+        let mut automaton = Self::new(7, &[6]);
+        automaton.add_transition(0, &local_part, 1);
+        automaton.add_transition(1, &local_part, 1);
+        automaton.add_transition(1, &[Char(b'@')], 2);
+        automaton.add_transition(2, &ALPHANUMERIC, 3);
+        automaton.add_transition(3, &label, 3);
+        automaton.add_transition(3, &dot, 4);
+        automaton.add_transition(4, &letter, 5);
+        automaton.add_transition(4, &digit_or_hyphen, 3);
+        for q in [5, 6] {
+            automaton.add_transition(q, &letter, 6);
+            automaton.add_transition(q, &digit_or_hyphen, 3);
+            automaton.add_transition(q, &dot, 4);
+        }
+        automaton
+    }
+
+    /// Automaton accepting the URLs `http://host` or `https://host`, optionally followed by a
+    /// port `:digits` and a path starting with `/` made of printable characters other than space.
+    /// The host is made of alphanumeric characters, `-` and `.`, starting with an alphanumeric
+    /// character.
+    fn url() -> Self {
+        let host = [DIGIT, LOWERCASE, UPPERCASE, Char(b'-'), Char(b'.')];
+        // States 0 to 8 read the scheme and "://", 9 the host, 10 and 11 the port, 12 the path.
+        let mut automaton = Self::new(13, &[9, 11, 12]);
+        for (i, c) in b"http".iter().enumerate() {
+            automaton.add_transition(i, &[Char(*c)], i + 1);
+        }
+        automaton.add_transition(4, &[Char(b's')], 5);
+        automaton.add_transition(4, &[Char(b':')], 6);
+        automaton.add_transition(5, &[Char(b':')], 6);
+        automaton.add_transition(6, &[Char(b'/')], 7);
+        automaton.add_transition(7, &[Char(b'/')], 8);
+        automaton.add_transition(8, &ALPHANUMERIC, 9);
+        automaton.add_transition(9, &host, 9);
+        automaton.add_transition(9, &[Char(b':')], 10);
+        automaton.add_transition(10, &[DIGIT], 11);
+        automaton.add_transition(11, &[DIGIT], 11);
+        automaton.add_transition(9, &[Char(b'/')], 12);
+        automaton.add_transition(11, &[Char(b'/')], 12);
+        automaton.add_transition(12, &[Range(b'!', b'~')], 12);
+        automaton
+    }
+}
+
+impl StringServerKey {
+    /// Returns an encryption of true if s is an email address `local@domain`. The local part is
+    /// made of ASCII alphanumeric characters and `._%+-`, the domain is made of at least two
+    /// labels of ASCII alphanumeric characters and `-` separated by dots, the last label being
+    /// made of at least two letters. s can have any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("john.doe@mail.com").unwrap();
+    /// let result = server_key.is_valid_email(&encrypted_str);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_valid_email(&self, s: &FheString) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::email())
+    }
+
+    /// Returns an encryption of true if s is an URL `http://host` or `https://host`, optionally
+    /// followed by a port `:digits` and a path starting with `/` without spaces. The host is made
+    /// of ASCII alphanumeric characters, `-` and `.`. s can have any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("https://zama.ai/about").unwrap();
+    /// let result = server_key.is_valid_url(&encrypted_str);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_valid_url(&self, s: &FheString) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::url())
+    }
+
+    /// Returns an encryption of true if s is an IPv4 address in dotted decimal notation, four
+    /// numbers in `0..=255` without leading zeros separated by dots. s can have any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("192.168.0.1").unwrap();
+    /// let result = server_key.is_valid_ipv4(&encrypted_str);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_valid_ipv4(&self, s: &FheString) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::ipv4())
+    }
+
+    /// Returns an encryption of true if s is a date `YYYY-MM-DD` with a month in `01..=12` and a
+    /// day in `01..=31`. The number of days of the month is not checked. s can have any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("2024-02-29").unwrap();
+    /// let result = server_key.is_iso_date(&encrypted_str);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_iso_date(&self, s: &FheString) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::iso_date())
+    }
+
+    /// Returns an encryption of true if s is non empty and made only of ASCII digits. s can have
+    /// any padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("0123").unwrap();
+    /// let result = server_key.is_numeric(&encrypted_str);
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn is_numeric(&self, s: &FheString) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::numeric())
+    }
+
+    /// Returns an encryption of true if s has the same length as the clear `mask` and each
+    /// character of s matches the character of the mask at the same index: `#` matches an ASCII
+    /// digit, `?` matches any character, and any other character matches itself. s can have any
+    /// padding.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("123-45-6789").unwrap();
+    /// let result = server_key.matches_mask(&encrypted_str, "###-##-####");
+    /// let clear_result = client_key.decrypt_integer(&server_key.bool_to_radix(&result));
+    /// assert_eq!(clear_result, 1);
+    /// ```
+    pub fn matches_mask(&self, s: &FheString, mask: &str) -> BooleanBlock {
+        self.run_automaton(s, &ClearAutomaton::mask(mask))
+    }
+
+    /// Runs the clear automaton on s and returns an encryption of true if it ends in an accepting
+    /// state. The current state is encrypted as one boolean per state. Padding zeros leave the
+    /// state unchanged, so s can have any padding. The states reachable after each number of
+    /// characters are known in clear, only their transitions are evaluated, and the membership of
+    /// a character to a class is computed once for all the transitions.
+    fn run_automaton(&self, s: &FheString, automaton: &ClearAutomaton) -> BooleanBlock {
+        let mut states: Vec<BooleanBlock> = (0..automaton.number_states)
+            .map(|q| self.create_bool(q == 0))
+            .collect();
+        let mut reachable = vec![false; automaton.number_states];
+        reachable[0] = true;
+
+        for c in &s.content {
+            let is_char = self.ne_clear_char(c, 0);
+            let mut classes: HashMap<CharClass, BooleanBlock> = HashMap::new();
+            let mut new_states = vec![self.create_false(); automaton.number_states];
+            let mut new_reachable = reachable.clone();
+            for (from, transition_classes, to) in &automaton.transitions {
+                if !reachable[*from] {
+                    continue;
+                }
+                let in_classes = transition_classes
+                    .iter()
+                    .map(|class| {
+                        classes
+                            .entry(*class)
+                            .or_insert_with(|| self.is_in_class(c, *class))
+                            .clone()
+                    })
+                    .reduce(|a, b| self.integer_key.boolean_bitor(&a, &b))
+                    .unwrap();
+                self.integer_key.boolean_bitor_assign(
+                    &mut new_states[*to],
+                    &self.integer_key.boolean_bitand(&states[*from], &in_classes),
+                );
+                new_reachable[*to] = true;
+            }
+            states = new_states
+                .iter()
+                .zip(states.iter())
+                .map(|(new_state, state)| self.update_state(&is_char, new_state, state))
+                .collect();
+            reachable = new_reachable;
+        }
+
+        automaton
+            .accepting_states
+            .iter()
+            .filter(|q| reachable[**q])
+            .map(|q| states[*q].clone())
+            .reduce(|a, b| self.integer_key.boolean_bitor(&a, &b))
+            .unwrap_or_else(|| self.create_false())
+    }
+
+    fn is_in_class(&self, c: &FheAsciiChar, class: CharClass) -> BooleanBlock {
+        match class {
+            Char(clear_char) => self.eq_clear_char(c, clear_char),
+            Range(low, high) => self.is_in_char_range(c, low, high),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{gen_keys_test, FheString};
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+    use tfhe::integer::BooleanBlock;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn check_validator(
+        validator: impl Fn(&StringServerKey, &FheString) -> BooleanBlock,
+        s: &str,
+        padding: usize,
+        expected: bool,
+    ) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let result = validator(&SERVER_KEY, &encrypted_s);
+        let clear_result = CLIENT_KEY.decrypt_bool(&result);
+        assert_eq!(clear_result, expected, "{}", s);
+    }
+
+    #[test]
+    fn test_is_valid_email() {
+        let is_valid_email = StringServerKey::is_valid_email;
+        check_validator(is_valid_email, "a.b@c.io", 2, true);
+        check_validator(is_valid_email, "a@b-2.c.org", 0, true);
+        check_validator(is_valid_email, "a@b.c", 0, false);
+        check_validator(is_valid_email, "a@b.c2", 0, false);
+        check_validator(is_valid_email, "@b.io", 0, false);
+        check_validator(is_valid_email, "a@b..io", 0, false);
+        check_validator(is_valid_email, "a@io", 1, false);
+    }
+
+    #[test]
+    fn test_is_valid_url() {
+        let is_valid_url = StringServerKey::is_valid_url;
+        check_validator(is_valid_url, "http://a.b", 1, true);
+        check_validator(is_valid_url, "https://a:80/x?y", 0, true);
+        check_validator(is_valid_url, "https://", 0, false);
+        check_validator(is_valid_url, "ftp://a.b", 0, false);
+        check_validator(is_valid_url, "http://a/b c", 0, false);
+    }
+
+    #[test]
+    fn test_is_valid_ipv4() {
+        let is_valid_ipv4 = StringServerKey::is_valid_ipv4;
+        check_validator(is_valid_ipv4, "255.249.0.10", 2, true);
+        check_validator(is_valid_ipv4, "1.2.3.199", 0, true);
+        check_validator(is_valid_ipv4, "1.2.3.256", 0, false);
+        check_validator(is_valid_ipv4, "1.2.3", 0, false);
+        check_validator(is_valid_ipv4, "01.2.3.4", 0, false);
+        check_validator(is_valid_ipv4, "1.2.3.4.", 1, false);
+    }
+
+    #[test]
+    fn test_is_iso_date() {
+        let is_iso_date = StringServerKey::is_iso_date;
+        check_validator(is_iso_date, "2024-12-31", 2, true);
+        check_validator(is_iso_date, "1999-01-09", 0, true);
+        check_validator(is_iso_date, "2024-13-01", 0, false);
+        check_validator(is_iso_date, "2024-00-10", 0, false);
+        check_validator(is_iso_date, "2024-01-32", 0, false);
+        check_validator(is_iso_date, "2024-1-10", 1, false);
+    }
+
+    #[test]
+    fn test_is_numeric_matches_mask() {
+        check_validator(StringServerKey::is_numeric, "0123", 2, true);
+        check_validator(StringServerKey::is_numeric, "", 1, false);
+        check_validator(StringServerKey::is_numeric, "12a", 0, false);
+        let ssn_mask = |sk: &StringServerKey, s: &FheString| sk.matches_mask(s, "###-##-?###");
+        check_validator(ssn_mask, "123-45-6789", 2, true);
+        check_validator(ssn_mask, "123-45-x789", 0, true);
+        check_validator(ssn_mask, "123-45-678", 0, false);
+        check_validator(ssn_mask, "123-4a-6789", 0, false);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::ciphertext::{FheAsciiChar, FheString};
+use crate::server_key::StringServerKey;
+use tfhe::integer::{BooleanBlock, RadixCiphertext};
+
+impl StringServerKey {
+    /// Counts the occurrences of each word of the clear `vocabulary` among the words of s. The
+    /// words of s are its maximal chunks of characters which are neither ASCII whitespaces nor
+    /// ASCII punctuation. Returns one encrypted count per word of the vocabulary, in the same order.
+    /// Empty words of the vocabulary are counted 0 times. If `ignore_case` is true, words are
+    /// compared ignoring the ASCII case.
+    ///
+    /// The punctuation of s is replaced by spaces, then s is chunked by `split_ascii_whitespace`
+    /// and each chunk is compared to each word with `eq` or `eq_ignore_case`.
+    /// # Examples
+    ///
+    /// ```
+    /// let (client_key, server_key) = gen_keys_test();
+    /// let encrypted_str = client_key.encrypt_str("The cat, the dog.").unwrap();
+    /// let counts = server_key.word_counts(&encrypted_str, &["the", "cat", "do"], true);
+    /// let clear_counts: Vec<u32> = counts
+    ///     .iter()
+    ///     .map(|count| client_key.decrypt_integer(count))
+    ///     .collect();
+    /// assert_eq!(clear_counts, vec![2, 1, 0]);
+    /// ```
+    pub fn word_counts(
+        &self,
+        s: &FheString,
+        vocabulary: &[&str],
+        ignore_case: bool,
+    ) -> Vec<RadixCiphertext> {
+        let space = FheAsciiChar(self.create_n(b' '));
+        let s = FheString {
+            content: s
+                .content
+                .iter()
+                .map(|c| self.cmux_char(&self.is_ascii_punctuation(c), &space, c))
+                .collect(),
+            padding: s.padding,
+            length: s.length.clone(),
+        };
+        let words = self.split_ascii_whitespace(&s);
+
+        vocabulary
+            .iter()
+            .map(|word| {
+                let mut count = self.create_zero();
+                if word.is_empty() {
+                    return count;
+                }
+                // The parts after the last word are empty, so they never match a non empty word.
+                for part in &words.parts {
+                    let is_word = match ignore_case {
+                        true => self.eq_ignore_case(part, word),
+                        false => self.eq(part, word),
+                    };
+                    self.integer_key
+                        .add_assign_parallelized(&mut count, &self.bool_to_radix(&is_word));
+                }
+                count
+            })
+            .collect()
+    }
+
+    /// Returns an encryption of true if c is an ASCII punctuation character.
+    pub fn is_ascii_punctuation(&self, c: &FheAsciiChar) -> BooleanBlock {
+        [(33, 47), (58, 64), (91, 96), (123, 126)]
+            .into_iter()
+            .map(|(low, high)| self.is_in_char_range(c, low, high))
+            .reduce(|a, b| self.integer_key.boolean_bitor(&a, &b))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::gen_keys_test;
+    use crate::client_key::StringClientKey;
+    use crate::server_key::StringServerKey;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref KEYS: (StringClientKey, StringServerKey) = gen_keys_test();
+        pub static ref CLIENT_KEY: &'static StringClientKey = &KEYS.0;
+        pub static ref SERVER_KEY: &'static StringServerKey = &KEYS.1;
+    }
+
+    fn word_counts(s: &str, vocabulary: &[&str], ignore_case: bool) -> Vec<u32> {
+        let words: Vec<&str> = s
+            .split(|c: char| c.is_ascii_whitespace() || c.is_ascii_punctuation())
+            .collect();
+        vocabulary
+            .iter()
+            .map(|word| {
+                if word.is_empty() {
+                    return 0;
+                }
+                words
+                    .iter()
+                    .filter(|w| match ignore_case {
+                        true => w.eq_ignore_ascii_case(word),
+                        false => *w == word,
+                    })
+                    .count() as u32
+            })
+            .collect()
+    }
+
+    fn check_word_counts(s: &str, padding: usize, vocabulary: &[&str], ignore_case: bool) {
+        let encrypted_s = CLIENT_KEY.encrypt_str_random_padding(s, padding).unwrap();
+        let counts = SERVER_KEY.word_counts(&encrypted_s, vocabulary, ignore_case);
+        let clear_counts: Vec<u32> = counts
+            .iter()
+            .map(|count| CLIENT_KEY.decrypt_integer(count))
+            .collect();
+        assert_eq!(clear_counts, word_counts(s, vocabulary, ignore_case));
+    }
+
+    #[test]
+    fn test_word_counts() {
+        let vocabulary = ["a", "ab", "Ab", "b", ""];
+        check_word_counts("ab a,Ab;b", 2, &vocabulary, false);
+        check_word_counts("ab a,Ab;b", 1, &vocabulary, true);
+        check_word_counts("aab\tab!", 0, &vocabulary, false);
+        check_word_counts("", 1, &vocabulary, true);
+    }
+}
@@ -19,17 +19,20 @@ macro_rules! display_result {
             0 => String::from("no padding"),
             _ => format!("{} padding zeros", $padding_zeros),
         };
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", $clear_s);
-        println!("    └ {}", padding_zeros_string);
-        println!("results:");
-        println!("{: <35} {:?}", "  ├ std result:", $std_result);
-        println!("{: <35} {:?}", "  └ FHE result:", $fhe_result);
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", $clear_s);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s, $padding_zeros);
+        display_line!("    └ {}", padding_zeros_string);
+        display_line!("results:");
+        display_line!("{: <35} {:?}", "  ├ std result:", $std_result);
+        display_line!("{: <35} {:?}", "  └ FHE result:", $fhe_result);
         if !$status.is_empty() {
-            println!("    └ {}", $status);
+            display_line!("    └ {}", $status);
         }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
 }
 
@@ -39,8 +42,9 @@ macro_rules! time_function {
         time_function!($method, $encrypted_s, $clear_s, 0)
     };
     ($method: ident, $encrypted_s: ident, $clear_s: ident, $padding_zeros: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s);
+        start.record_pbs_count();
         let fhe_result = CLIENT_KEY.decrypt_string(&encrypted_fhe_result).unwrap();
         let duration = start.elapsed();
         let std_result = $clear_s.$method();
@@ -71,18 +75,22 @@ macro_rules! display_result_string_pattern {
             (1, result_string) => format!("Some({:?})", result_string),
             _ => "None".to_string(),
         };
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", $clear_s);
-        println!("    └ {}", string_padding_zeros_string);
-        println!("{: <35} {:?}", "  └ clear string pattern", $clear_pattern);
-        println!("results:");
-        println!("{: <35} {:}", "  ├ std result:", std_result_string);
-        println!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", $clear_s);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s, $string_padding);
+        display_line!("    └ {}", string_padding_zeros_string);
+        display_line!("{: <35} {:?}", "  └ clear string pattern", $clear_pattern);
+        $crate::timing_report::record_argument("clear string pattern", &$clear_pattern, 0);
+        display_line!("results:");
+        display_line!("{: <35} {:}", "  ├ std result:", std_result_string);
+        display_line!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
         // if !$status.is_empty() {
         //     println!("    └ {}", $status);
         // }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
     ($method: ident, $clear_s: expr, $clear_pattern: expr, $fhe_result: expr, $std_result: expr, $duration: expr, $string_padding: expr, $pattern_padding: expr) => {
         let string_padding_zeros_string = match $string_padding {
@@ -101,22 +109,31 @@ macro_rules! display_result_string_pattern {
             (1, result_string) => format!("Some({:?})", result_string),
             _ => "None".to_string(),
         };
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", $clear_s);
-        println!("    └ {}", string_padding_zeros_string);
-        println!(
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", $clear_s);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s, $string_padding);
+        display_line!("    └ {}", string_padding_zeros_string);
+        display_line!(
             "{: <35} {:?}",
-            "  └ encrypted string pattern", $clear_pattern
+            "  └ encrypted string pattern",
+            $clear_pattern
         );
-        println!("    └ {}", pattern_padding_zeros_string);
-        println!("results:");
-        println!("{: <35} {:}", "  ├ std result:", std_result_string);
-        println!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
+        display_line!("    └ {}", pattern_padding_zeros_string);
+        $crate::timing_report::record_argument(
+            "encrypted string pattern",
+            &$clear_pattern,
+            $pattern_padding,
+        );
+        display_line!("results:");
+        display_line!("{: <35} {:}", "  ├ std result:", std_result_string);
+        display_line!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
         // if !$status.is_empty() {
         //     println!("    └ {}", $status);
         // }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
 }
 
@@ -164,18 +181,22 @@ macro_rules! display_result_string_pattern_return_type {
         //     (1, result_string) => format!("Some({:?})", result_string),
         //     _ => "None".to_string(),
         // };
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", $clear_s);
-        println!("    └ {}", string_padding_zeros_string);
-        println!("{: <35} {:?}", "  └ clear string pattern", $clear_pattern);
-        println!("results:");
-        println!("{: <35} {:}", "  ├ std result:", std_result_string);
-        println!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", $clear_s);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s, $string_padding);
+        display_line!("    └ {}", string_padding_zeros_string);
+        display_line!("{: <35} {:?}", "  └ clear string pattern", $clear_pattern);
+        $crate::timing_report::record_argument("clear string pattern", &$clear_pattern, 0);
+        display_line!("results:");
+        display_line!("{: <35} {:}", "  ├ std result:", std_result_string);
+        display_line!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
         // if !$status.is_empty() {
         //     println!("    └ {}", $status);
         // }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
     ($method: ident, $return_type: ident $clear_s: expr, $clear_pattern: expr, $fhe_result: expr, $std_result: expr, $duration: expr, $string_padding: expr, $pattern_padding: expr) => {
         let string_padding_zeros_string = match $string_padding {
@@ -196,22 +217,31 @@ macro_rules! display_result_string_pattern_return_type {
         //     (1, result_string) => format!("Some({:?})", result_string),
         //     _ => "None".to_string(),
         // };
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", $clear_s);
-        println!("    └ {}", string_padding_zeros_string);
-        println!(
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", $clear_s);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s, $string_padding);
+        display_line!("    └ {}", string_padding_zeros_string);
+        display_line!(
             "{: <35} {:?}",
-            "  └ encrypted string pattern", $clear_pattern
+            "  └ encrypted string pattern",
+            $clear_pattern
+        );
+        display_line!("    └ {}", pattern_padding_zeros_string);
+        $crate::timing_report::record_argument(
+            "encrypted string pattern",
+            &$clear_pattern,
+            $pattern_padding,
         );
-        println!("    └ {}", pattern_padding_zeros_string);
-        println!("results:");
-        println!("{: <35} {:}", "  ├ std result:", std_result_string);
-        println!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
+        display_line!("results:");
+        display_line!("{: <35} {:}", "  ├ std result:", std_result_string);
+        display_line!("{: <35} {:}", "  └ FHE result:", fhe_result_string);
         // if !$status.is_empty() {
         //     println!("    └ {}", $status);
         // }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
 }
 
@@ -219,8 +249,9 @@ macro_rules! display_result_string_pattern_return_type {
 macro_rules! time_function_string_pattern_return_type {
     // unpadded string, clear pattern
     ($method: ident, $return_type: ident, $encrypted_s: ident, $clear_s: ident, $clear_pattern: ident) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s, &$clear_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -240,8 +271,9 @@ macro_rules! time_function_string_pattern_return_type {
     };
     // padded string, clear pattern
     ($method: ident, $return_type: ident, $encrypted_s_padded: ident, $string_padding: expr, $clear_s: ident,  $clear_pattern: ident) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s_padded, &$clear_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -261,8 +293,9 @@ macro_rules! time_function_string_pattern_return_type {
     };
     // encrypted string encrypted_pattern
     ($method: ident, $return_type: ident, $encrypted_s: ident, $string_padding: expr, $clear_s: ident,  $clear_pattern:ident, $encrypted_pattern: ident, $pattern_padding: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s, &$encrypted_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -286,8 +319,9 @@ macro_rules! time_function_string_pattern_return_type {
 macro_rules! time_function_string_pattern {
     // unpadded string, clear pattern
     ($method: ident, $encrypted_s: ident, $clear_s: ident, $clear_pattern: ident) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s, &$clear_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -306,8 +340,9 @@ macro_rules! time_function_string_pattern {
     };
     // padded string, clear pattern
     ($method: ident, $encrypted_s_padded: ident, $string_padding: expr, $clear_s: ident,  $clear_pattern: ident) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s_padded, &$clear_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -326,8 +361,9 @@ macro_rules! time_function_string_pattern {
     };
     // encrypted string encrypted_pattern
     ($method: ident, $encrypted_s: ident, $string_padding: expr, $clear_s: ident,  $clear_pattern:ident, $encrypted_pattern: ident, $pattern_padding: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s, &$encrypted_pattern);
+        start.record_pbs_count();
         let fhe_result = (
             CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.0),
             CLIENT_KEY.decrypt_string(&encrypted_fhe_result.1).unwrap(),
@@ -353,8 +389,9 @@ macro_rules! time_fhe_split {
         time_fhe_split!($method, $encrypted_s, $clear_s, 0)
     };
     ($method: ident, $encrypted_s_padding: ident, $clear_s: ident, $padding_zeros: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let encrypted_fhe_result = SERVER_KEY.$method(&$encrypted_s_padding);
+        start.record_pbs_count();
         let duration = start.elapsed();
         let std_result: Vec<String> = $clear_s.$method().map(|s| String::from(s)).collect();
         let clear_len = CLIENT_KEY.decrypt_integer(&encrypted_fhe_result.number_parts);
@@ -379,13 +416,14 @@ macro_rules! time_len {
         time_len!($method, $encrypted_s, $clear_s, 0)
     };
     ($method: ident, $encrypted_s: ident, $clear_s: ident, $padding_zeros: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let fhe_result = match SERVER_KEY.$method(&$encrypted_s) {
             FheStrLength::Encrypted(encrypted_length) => {
                 CLIENT_KEY.decrypt_integer(&encrypted_length)
             }
             FheStrLength::Clear(clear_length) => *clear_length as u32,
         };
+        start.record_pbs_count();
         let duration = start.elapsed();
         let std_result = $clear_s.$method();
         display_result!(
@@ -405,7 +443,7 @@ macro_rules! time_is_empty {
         time_is_empty!($method, $encrypted_s, $clear_s, 0)
     };
     ($method: ident, $encrypted_s: ident, $clear_s: ident, $padding_zeros: expr) => {
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let (fhe_result, encryption_status) = match &SERVER_KEY.$method(&$encrypted_s) {
             FheBool::Encrypted(encrypted_bool) => (
                 CLIENT_KEY.decrypt_integer(&SERVER_KEY.bool_to_radix(encrypted_bool)) != 0,
@@ -413,6 +451,7 @@ macro_rules! time_is_empty {
             ),
             FheBool::Clear(clear_bool) => (*clear_bool, "clear"),
         };
+        start.record_pbs_count();
         let duration = start.elapsed();
         let std_result = $clear_s.$method();
         display_result!(
@@ -10,28 +10,34 @@
 macro_rules! display_arg {
     (String, $clear_arg: ident, Clear, $padding_zeros: expr, $rank: expr) => {
         let string_title = format!("  └ {:} clear string", $rank);
-        println!("{: <35} {:?}", string_title, $clear_arg);
+        display_line!("{: <35} {:?}", string_title, $clear_arg);
+        $crate::timing_report::record_argument("clear string", &$clear_arg, 0);
     };
     (String, $clear_arg: ident, Encrypted, $padding_zeros: expr, $rank: expr) => {
         let string_title = format!("  └ {:} encrypted string", $rank);
-        println!("{: <35} {:?}", string_title, $clear_arg);
-        println!("    └ {}", padding_to_string($padding_zeros));
+        display_line!("{: <35} {:?}", string_title, $clear_arg);
+        $crate::timing_report::record_argument("encrypted string", &$clear_arg, $padding_zeros);
+        display_line!("    └ {}", padding_to_string($padding_zeros));
     };
     (char, $clear_arg: ident, Clear, $padding_zeros: expr, $rank: expr) => {
         let pattern_title = format!("  └ {:} clear character pattern", $rank);
-        println!("{: <35} {:?}", pattern_title, $clear_arg);
+        display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+        $crate::timing_report::record_argument("clear character pattern", &$clear_arg, 0);
     };
     (char, $clear_arg: ident, Encrypted, $padding_zeros: expr, $rank: expr) => {
         let pattern_title = format!("  └ {:} encrypted character pattern", $rank);
-        println!("{: <35} {:?}", pattern_title, $clear_arg);
+        display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+        $crate::timing_report::record_argument("encrypted character pattern", &$clear_arg, 0);
     };
     (usize, $clear_arg: ident, Clear, $padding_zeros: expr, $rank: expr) => {
         let pattern_title = format!("  └ {:} clear integer argument", $rank);
-        println!("{: <35} {:?}", pattern_title, $clear_arg);
+        display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+        $crate::timing_report::record_argument("clear integer argument", &$clear_arg, 0);
     };
     (usize, $clear_arg: ident, Encrypted, $padding_zeros: expr, $rank: expr) => {
         let pattern_title = format!("  └ {:} encrypted integer argument", $rank);
-        println!("{: <35} {:?}", pattern_title, $clear_arg);
+        display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+        $crate::timing_report::record_argument("encrypted integer argument", &$clear_arg, 0);
     };
 }
 
@@ -47,19 +53,21 @@ pub fn padding_to_string(padding_zeros: usize) -> String {
 macro_rules! display_timing {
     ($clear_s: ident, $padding_s: expr, $displayable_std_result: ident, $displayable_fhe_result: ident, $return_type: ident, $duration: ident,
      $(($arg_type: ident, $clear_arg: ident, $encryption: ident, $arg_padding: expr)),*) => {
-	println!("arguments:");
-	println!("{: <35} {:?}", "  └ encrypted string", &$clear_s);
+	display_line!("arguments:");
+	display_line!("{: <35} {:?}", "  └ encrypted string", &$clear_s);
+	$crate::timing_report::record_argument("encrypted string", &$clear_s, $padding_s);
         let padding_string = padding_to_string($padding_s);
-        println!("    └ {}", padding_string);
+        display_line!("    └ {}", padding_string);
 
 	$(
 	   display_arg!($arg_type, $clear_arg, $encryption, $arg_padding, "");
 	)*
 
-	println!("results:");
-        println!("{: <35} {:?}", "  ├ std result:", $displayable_std_result);
-        println!("{: <35} {:?}", "  └ FHE result:", $displayable_fhe_result);
-        println!("time:                               {:?}", $duration);
+	display_line!("results:");
+        display_line!("{: <35} {:?}", "  ├ std result:", $displayable_std_result);
+        display_line!("{: <35} {:?}", "  └ FHE result:", $displayable_fhe_result);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
 }
 
@@ -146,13 +154,14 @@ macro_rules! time_patterns {
 		0 =>  ref_or_clone!($method, $encrypted_s),
 		_ =>  ref_or_clone!($method, $encrypted_s_padding),
 	    };
-	    let start = std::time::Instant::now();
+	    let start = $crate::timing_report::start_timing();
 	    let fhe_result = SERVER_KEY.$method(
 		encrypted_s,
 		$( // Use rep macro to generate the list of argument to the FHE method
 		    pick_arg!($clear_arg, $encrypted_arg, $encrypted_arg_padding, $encryption, $arg_padding),
 		 )*
 	    );
+	    start.record_pbs_count();
 	    let duration = start.elapsed();
 	    let string_fhe_result = to_string_fhe_result!(fhe_result, $return_type);
 	   // println!("{:?}", string_fhe_result);
@@ -166,7 +175,8 @@ macro_rules! time_patterns {
 
 //    ($clear_s: ident, $displayable_std_result: ident, $displayable_fhe_result: ident, $return_type: ident, $duration: ident,
 	    //   $(($arg_type: ident, $clear_arg: ident, $encryption: ident, $arg_padding: expr)),*)
-	    println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+	    display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+	    $crate::timing_report::begin_record(std::stringify!($method));
 	    display_timing!($clear_s, $padding_s, displayable_std_result, string_fhe_result, $return_type, duration,
 	    $(($arg_type, $clear_arg, $encryption, $arg_padding)),*);
 
@@ -344,12 +354,18 @@ macro_rules! print_arg {
         match $encryption {
             Encryption::Clear => {
                 let string_title = format!("  └ {:} clear string", $rank);
-                println!("{: <35} {:?}", string_title, $clear_arg);
+                display_line!("{: <35} {:?}", string_title, $clear_arg);
+                $crate::timing_report::record_argument("clear string", &$clear_arg, 0);
             }
             Encryption::Encrypted => {
                 let string_title = format!("  └ {:} encrypted string", $rank);
-                println!("{: <35} {:?}", string_title, $clear_arg);
-                println!("    └ {}", padding_to_string($padding_zeros));
+                display_line!("{: <35} {:?}", string_title, $clear_arg);
+                $crate::timing_report::record_argument(
+                    "encrypted string",
+                    &$clear_arg,
+                    $padding_zeros,
+                );
+                display_line!("    └ {}", padding_to_string($padding_zeros));
             }
         }
     };
@@ -358,11 +374,17 @@ macro_rules! print_arg {
         match $encryption {
             Encryption::Clear => {
                 let pattern_title = format!("  └ {:} clear character pattern", $rank);
-                println!("{: <35} {:?}", pattern_title, $clear_arg);
+                display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+                $crate::timing_report::record_argument("clear character pattern", &$clear_arg, 0);
             }
             Encryption::Encrypted => {
                 let pattern_title = format!("  └ {:} encrypted character pattern", $rank);
-                println!("{: <35} {:?}", pattern_title, $clear_arg);
+                display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+                $crate::timing_report::record_argument(
+                    "encrypted character pattern",
+                    &$clear_arg,
+                    0,
+                );
             }
         }
     };
@@ -371,11 +393,17 @@ macro_rules! print_arg {
         match $encryption {
             Encryption::Clear => {
                 let pattern_title = format!("  └ {:} clear integer argument", $rank);
-                println!("{: <35} {:?}", pattern_title, $clear_arg);
+                display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+                $crate::timing_report::record_argument("clear integer argument", &$clear_arg, 0);
             }
             Encryption::Encrypted => {
                 let pattern_title = format!("  └ {:} encrypted integer argument", $rank);
-                println!("{: <35} {:?}", pattern_title, $clear_arg);
+                display_line!("{: <35} {:?}", pattern_title, $clear_arg);
+                $crate::timing_report::record_argument(
+                    "encrypted integer argument",
+                    &$clear_arg,
+                    0,
+                );
             }
         }
     };
@@ -385,10 +413,11 @@ macro_rules! print_arg {
 #[macro_export]
 macro_rules! display_result_pair {
     ($clear_s1: ident, $clear_pattern: ident, $string_std_result: ident, $string_fhe_result: ident, $return_type: ident, $arg_type: ident , $padding_s1: expr, $padding_s2: expr, $duration: ident, $encryption: expr) => {
-        println!("arguments:");
-        println!("{: <35} {:?}", "  └ encrypted string", &$clear_s1);
+        display_line!("arguments:");
+        display_line!("{: <35} {:?}", "  └ encrypted string", &$clear_s1);
+        $crate::timing_report::record_argument("encrypted string", &$clear_s1, $padding_s1);
         let padding_string = padding_to_string($padding_s1);
-        println!("    └ {}", padding_string);
+        display_line!("    └ {}", padding_string);
         let encryption: Encryption = $encryption;
         print_arg!(
             $arg_type,
@@ -399,13 +428,14 @@ macro_rules! display_result_pair {
         );
         //print_char_arg($clear_pattern, $encryption, "".to_string());
         //        println!("{: <35} {:?}", "  └ clear string pattern", $clear_pattern);
-        println!("results:");
-        println!("{: <35} {:?}", "  ├ std result:", $string_std_result);
-        println!("{: <35} {:?}", "  └ FHE result:", $string_fhe_result);
+        display_line!("results:");
+        display_line!("{: <35} {:?}", "  ├ std result:", $string_std_result);
+        display_line!("{: <35} {:?}", "  └ FHE result:", $string_fhe_result);
         // if !$status.is_empty() {
         //     println!("    └ {}", $status);
         // }
-        println!("time:                               {:?}", $duration);
+        display_line!("time:                               {:?}", $duration);
+        $crate::timing_report::end_record($duration);
     };
 }
 
@@ -518,16 +548,18 @@ macro_rules! time_pair_string {
      $return_type: ident, $pattern_type: ident, $padding_s1: expr, $padding_s2: expr, $encryption: ident) => {
         let encrypted_s1 = ref_or_clone!($method, $encrypted_s1);
         //let mut encrypted_s1 = $encrypted_s1.clone();
-        let start = std::time::Instant::now();
+        let start = $crate::timing_report::start_timing();
         let fhe_result = match $encryption {
             Encryption::Clear => SERVER_KEY.$method(encrypted_s1, &$clear_s2),
             Encryption::Encrypted => SERVER_KEY.$method(encrypted_s1, &$encrypted_s2),
         };
+        start.record_pbs_count();
         let duration = start.elapsed();
         let string_fhe_result = to_string_fhe_result!(fhe_result, $return_type);
         let string_std_result =
             to_string_std_result!($method, $clear_s1, $clear_s2, $return_type, $pattern_type);
-        println!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        display_line!("\n\n\n{: <35} {}", "function:", std::stringify!($method));
+        $crate::timing_report::begin_record(std::stringify!($method));
         display_result_pair!(
             $clear_s1,
             $clear_s2,
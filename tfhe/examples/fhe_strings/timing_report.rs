@@ -0,0 +1,161 @@
+//! This file contains the machine readable output of the timing macros. Each timed function call
+//! produces a record with the name of the function, the kind, padding and length of its arguments,
+//! its duration and the number of integer operations it performed. The record is built step by
+//! step by the timing macros while they display the human readable output, and is emitted as a
+//! JSON or CSV line depending on the output format.
+use crate::SERVER_KEY;
+use clap::ValueEnum;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Human readable trees, one per timed function call.
+    Text,
+    /// One JSON object per line and per timed function call.
+    Json,
+    /// One CSV row per timed function call, after a header row.
+    Csv,
+}
+
+#[derive(Serialize, Default)]
+struct TimingRecord {
+    function: String,
+    /// Kind of each argument, for instance "encrypted string" or "clear character pattern".
+    arguments: Vec<String>,
+    /// Number of padding zeros of each argument.
+    paddings: Vec<usize>,
+    /// Length of each argument, 1 for characters and 0 for integers.
+    input_lengths: Vec<usize>,
+    duration_ns: u128,
+    /// Number of integer operations performed by the server key, each one performing one or
+    /// several PBS.
+    operations: usize,
+}
+
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+static CSV_HEADER_WRITTEN: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref CURRENT_RECORD: Mutex<TimingRecord> = Mutex::new(TimingRecord::default());
+}
+
+/// Prints a line of the human readable output, only if the output format is text.
+#[macro_export]
+macro_rules! display_line {
+    ($($arg: tt)*) => {
+        if $crate::timing_report::is_text_output() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Sets the output format, the default being text.
+pub fn set_output_format(format: OutputFormat) {
+    OUTPUT_FORMAT.set(format).unwrap();
+}
+
+pub fn is_text_output() -> bool {
+    *OUTPUT_FORMAT.get().unwrap_or(&OutputFormat::Text) == OutputFormat::Text
+}
+
+/// Length of a clear argument of a timed function.
+pub trait ClearArgument {
+    fn input_length(&self) -> usize;
+}
+
+impl ClearArgument for str {
+    fn input_length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ClearArgument for String {
+    fn input_length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ClearArgument for char {
+    fn input_length(&self) -> usize {
+        1
+    }
+}
+
+impl ClearArgument for usize {
+    fn input_length(&self) -> usize {
+        0
+    }
+}
+
+impl<T: ClearArgument + ?Sized> ClearArgument for &T {
+    fn input_length(&self) -> usize {
+        (**self).input_length()
+    }
+}
+
+/// The start of the timing of a function call.
+pub struct TimingStart(Instant);
+
+impl TimingStart {
+    /// Records the number of integer operations performed by the server key since the start. It
+    /// should be called right after the timed function returns.
+    pub fn record_pbs_count(&self) {
+        CURRENT_RECORD.lock().unwrap().operations = SERVER_KEY.integer_key.operations_count();
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// Starts timing a function call, resetting the operation counter of the server key.
+pub fn start_timing() -> TimingStart {
+    SERVER_KEY.integer_key.reset_operations_count();
+    TimingStart(Instant::now())
+}
+
+/// Starts a new record for the timed function, keeping the operation count of the last timing.
+pub fn begin_record(function: &str) {
+    let mut record = CURRENT_RECORD.lock().unwrap();
+    *record = TimingRecord {
+        function: function.to_string(),
+        operations: record.operations,
+        ..TimingRecord::default()
+    };
+}
+
+pub fn record_argument(kind: &str, argument: &impl ClearArgument, padding_zeros: usize) {
+    let mut record = CURRENT_RECORD.lock().unwrap();
+    record.arguments.push(kind.to_string());
+    record.paddings.push(padding_zeros);
+    record.input_lengths.push(argument.input_length());
+}
+
+/// Completes the current record with its duration and emits it, if the output format is not text.
+pub fn end_record(duration: Duration) {
+    let mut record = CURRENT_RECORD.lock().unwrap();
+    record.duration_ns = duration.as_nanos();
+    match OUTPUT_FORMAT.get().unwrap_or(&OutputFormat::Text) {
+        OutputFormat::Text => (),
+        OutputFormat::Json => println!("{}", serde_json::to_string(&*record).unwrap()),
+        OutputFormat::Csv => {
+            if !CSV_HEADER_WRITTEN.swap(true, Ordering::Relaxed) {
+                println!("function,arguments,paddings,input_lengths,duration_ns,operations");
+            }
+            let join = |values: Vec<String>| values.join(";");
+            println!(
+                "{},{},{},{},{},{}",
+                record.function,
+                join(record.arguments.clone()),
+                join(record.paddings.iter().map(|p| p.to_string()).collect()),
+                join(record.input_lengths.iter().map(|l| l.to_string()).collect()),
+                record.duration_ns,
+                record.operations
+            );
+        }
+    }
+}